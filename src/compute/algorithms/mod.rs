@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Serialize, Deserialize};
 use async_trait::async_trait;
 
@@ -18,6 +19,9 @@ pub type Graph = HashMap<NodeId, Vec<(NodeId, Weight)>>;
 pub type Communities = HashMap<NodeId, usize>;
 pub type AnalysisResult = HashMap<String, serde_json::Value>;
 
+/// Callback through which long-running algorithms publish intermediate results
+pub type PartialResultSink = Arc<dyn Fn(serde_json::Value) + Send + Sync>;
+
 #[async_trait]
 pub trait AnalysisAlgorithm {
     type Input;
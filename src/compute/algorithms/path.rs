@@ -1,17 +1,20 @@
 use std::collections::{HashMap, BinaryHeap, HashSet};
 use std::cmp::Ordering;
+use std::time::Instant;
 use async_trait::async_trait;
 use serde_json::json;
 
 use super::{
     AnalysisAlgorithm, Graph, NodeId, Weight, AnalysisResult,
-    PathParams, PathType, PathWeightFunction,
+    PathParams, PathType, PathWeightFunction, PartialResultSink,
 };
-use crate::error::Result;
+use crate::error::{Error, Result};
 
 pub struct PathAnalysis {
     algorithm: PathType,
     params: PathParams,
+    partial_sink: Option<PartialResultSink>,
+    deadline: Option<Instant>,
 }
 
 #[derive(Debug, Clone)]
@@ -52,7 +55,26 @@ impl Eq for State {}
 
 impl PathAnalysis {
     pub fn new(algorithm: PathType, params: PathParams) -> Self {
-        Self { algorithm, params }
+        Self {
+            algorithm,
+            params,
+            partial_sink: None,
+            deadline: None,
+        }
+    }
+
+    /// Publish each path to `sink` as soon as it is discovered
+    pub fn with_partial_sink(mut self, sink: PartialResultSink) -> Self {
+        self.partial_sink = Some(sink);
+        self
+    }
+
+    /// Stop the all-paths search once `deadline` passes. The result then
+    /// holds the paths found so far and a `"timeout"` entry saying where the
+    /// search stopped.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
     }
 
     fn find_shortest_path(
@@ -119,18 +141,20 @@ impl PathAnalysis {
         Ok(None)
     }
 
+    /// All simple paths from `start` to `end`, plus the timeout message if
+    /// the deadline cut the search short
     fn find_all_paths(
         &self,
         graph: &Graph,
         start: &NodeId,
         end: &NodeId,
-    ) -> Result<Vec<Path>> {
+    ) -> Result<(Vec<Path>, Option<String>)> {
         let mut all_paths = Vec::new();
         let mut current_path = vec![*start];
         let mut visited = HashSet::new();
         visited.insert(*start);
 
-        self.dfs_paths(
+        let search = self.dfs_paths(
             graph,
             start,
             end,
@@ -138,9 +162,13 @@ impl PathAnalysis {
             &mut visited,
             &mut all_paths,
             0.0,
-        )?;
+        );
 
-        Ok(all_paths)
+        match search {
+            Ok(()) => Ok((all_paths, None)),
+            Err(Error::Timeout(message)) => Ok((all_paths, Some(message))),
+            Err(e) => Err(e),
+        }
     }
 
     fn dfs_paths(
@@ -153,11 +181,24 @@ impl PathAnalysis {
         all_paths: &mut Vec<Path>,
         weight_so_far: Weight,
     ) -> Result<()> {
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Err(Error::timeout(format!(
+                    "All-paths search stopped after finding {} paths",
+                    all_paths.len()
+                )));
+            }
+        }
+
         if current == end {
-            all_paths.push(Path {
+            let found = Path {
                 nodes: path.clone(),
                 total_weight: weight_so_far,
-            });
+            };
+            if let Some(sink) = &self.partial_sink {
+                sink(Self::path_to_json(&found));
+            }
+            all_paths.push(found);
             return Ok(());
         }
 
@@ -214,17 +255,19 @@ impl PathAnalysis {
         analyzer.find_shortest_path(graph, start, end)
     }
 
+    fn path_to_json(path: &Path) -> serde_json::Value {
+        json!({
+            "nodes": path.nodes.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+            "weight": path.total_weight,
+        })
+    }
+
     fn convert_to_analysis_result(&self, paths: Vec<Path>) -> AnalysisResult {
         let mut result = HashMap::new();
         
         // Convert paths to serializable format
-        let path_data: Vec<_> = paths.into_iter()
-            .map(|path| {
-                json!({
-                    "nodes": path.nodes.iter().map(|n| n.to_string()).collect::<Vec<_>>(),
-                    "weight": path.total_weight,
-                })
-            })
+        let path_data: Vec<_> = paths.iter()
+            .map(Self::path_to_json)
             .collect();
 
         result.insert("paths".to_string(), json!(path_data));
@@ -256,6 +299,7 @@ impl AnalysisAlgorithm for PathAnalysis {
 
     async fn execute(&self, input: Self::Input) -> Result<AnalysisResult> {
         let (graph, start, end) = input;
+        let mut timeout = None;
         let paths = match self.algorithm {
             PathType::ShortestPath => {
                 self.find_shortest_path(&graph, &start, &end)?
@@ -263,7 +307,9 @@ impl AnalysisAlgorithm for PathAnalysis {
                     .unwrap_or_default()
             }
             PathType::AllPaths => {
-                self.find_all_paths(&graph, &start, &end)?
+                let (paths, stopped) = self.find_all_paths(&graph, &start, &end)?;
+                timeout = stopped;
+                paths
             }
             PathType::CriticalPath => {
                 self.find_critical_path(&graph, &start, &end)?
@@ -272,6 +318,10 @@ impl AnalysisAlgorithm for PathAnalysis {
             }
         };
 
-        Ok(self.convert_to_analysis_result(paths))
+        let mut result = self.convert_to_analysis_result(paths);
+        if let Some(message) = timeout {
+            result.insert("timeout".to_string(), json!(message));
+        }
+        Ok(result)
     }
 } 
\ No newline at end of file
@@ -1,6 +1,6 @@
-use std::sync::Arc;
+use std::sync::{mpsc, Arc, Mutex};
 use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 use rayon::ThreadPool;
 use uuid::Uuid;
@@ -9,15 +9,18 @@ use sysinfo::{System, SystemExt, ProcessExt};
 use crate::error::{Error, Result};
 use super::{
     ComputeConfig, ComputeStats,
-    task::{ComputeTask, TaskHandle, ComputeResult, TaskStatus},
+    task::{ComputeTask, TaskHandle, ComputeResult, PartialResult, TaskStatus},
     algorithms::{
         AnalysisAlgorithm, CentralityAnalysis, CommunityDetection, PathAnalysis,
         CentralityParams, CommunityParams, PathParams, PathWeightFunction,
-        Graph, NodeId,
+        Graph, NodeId, PartialResultSink,
     },
     AnalysisType,
 };
 
+/// Number of recent partial results kept per task for late subscribers
+const MAX_PARTIAL_REPLAY: usize = 256;
+
 pub struct ComputeEngine {
     config: ComputeConfig,
     thread_pool: Arc<ThreadPool>,
@@ -25,6 +28,16 @@ pub struct ComputeEngine {
     results: Arc<RwLock<HashMap<Uuid, ComputeResult>>>,
     stats: Arc<RwLock<ComputeStats>>,
     sys_info: Arc<RwLock<System>>,
+    partials: Arc<Mutex<HashMap<Uuid, PartialChannel>>>,
+}
+
+/// The most recent partial results of a running task, and the consumers
+/// currently streaming them
+#[derive(Default)]
+struct PartialChannel {
+    published: usize,
+    history: VecDeque<PartialResult>,
+    subscribers: Vec<mpsc::Sender<PartialResult>>,
 }
 
 impl ComputeEngine {
@@ -50,6 +63,7 @@ impl ComputeEngine {
                 memory_usage: 0,
             })),
             sys_info: Arc::new(RwLock::new(sys)),
+            partials: Arc::new(Mutex::new(HashMap::new())),
         })
     }
 
@@ -76,6 +90,7 @@ impl ComputeEngine {
         
         // Store task handle
         self.tasks.write().await.insert(task.id, handle.clone());
+        self.partials.lock()?.insert(task.id, PartialChannel::default());
         
         // Update stats
         let mut stats = self.stats.write().await;
@@ -87,7 +102,13 @@ impl ComputeEngine {
         let stats = Arc::clone(&self.stats);
         let thread_pool = Arc::clone(&self.thread_pool);
         let sys_info = Arc::clone(&self.sys_info);
+        let partials = Arc::clone(&self.partials);
         let task_id = task.id;
+        // The analysis config may set a tighter limit than the task itself
+        let timeout = match task.analysis_config.timeout {
+            limit if limit.is_zero() => task.timeout,
+            limit => limit.min(task.timeout),
+        };
 
         // Spawn task execution
         tokio::spawn(async move {
@@ -98,7 +119,15 @@ impl ComputeEngine {
                 task_handle.status = TaskStatus::Running;
             }
 
-            let result = Self::execute_task(task, thread_pool).await;
+            // CPU-bound algorithms check the deadline themselves; the outer
+            // timeout catches ones that yield
+            let partial_sink = Self::partial_sink(Arc::clone(&partials), task_id);
+            let deadline = start_time + timeout;
+            let execution = Self::execute_task(task, thread_pool, partial_sink, deadline);
+            let result = match tokio::time::timeout(timeout, execution).await {
+                Ok(result) => result,
+                Err(_) => Err(Error::timeout(format!("Task exceeded its {:?} timeout", timeout))),
+            };
             let duration = start_time.elapsed();
 
             // Get memory usage
@@ -115,10 +144,33 @@ impl ComputeEngine {
                 Ok(compute_result) => {
                     results.insert(compute_result.task_id, compute_result.clone());
                     if let Some(task_handle) = tasks.get_mut(&compute_result.task_id) {
-                        task_handle.status = TaskStatus::Completed;
-                        task_handle.progress = 1.0;
+                        task_handle.status = compute_result.status;
+                        if !compute_result.timed_out {
+                            task_handle.progress = 1.0;
+                        }
+                    }
+                    if compute_result.timed_out {
+                        stats.failed_tasks += 1;
+                    } else {
+                        stats.completed_tasks += 1;
                     }
-                    stats.completed_tasks += 1;
+                }
+                Err(Error::Timeout(message)) => {
+                    // The algorithm never got to hand back what it found.
+                    // Subscribers already saw its partials as they came;
+                    // the capped replay history is not a complete result.
+                    let compute_result = ComputeResult::timeout(
+                        task_id,
+                        message.clone(),
+                        None,
+                        duration,
+                        memory_used,
+                    );
+                    results.insert(task_id, compute_result);
+                    if let Some(task_handle) = tasks.get_mut(&task_id) {
+                        task_handle.status = TaskStatus::TimedOut;
+                    }
+                    stats.failed_tasks += 1;
                 }
                 Err(error) => {
                     let compute_result = ComputeResult::failure(
@@ -146,6 +198,11 @@ impl ComputeEngine {
                         / stats.completed_tasks as f64,
                 );
             }
+
+            // The final result is stored; close any partial result streams
+            if let Ok(mut partials) = partials.lock() {
+                partials.remove(&task_id);
+            }
         });
 
         Ok(handle)
@@ -159,6 +216,46 @@ impl ComputeEngine {
             .ok_or_else(|| Error::computation(format!("No result found for task {}", handle.id)))
     }
 
+    /// Stream the intermediate results of a task. Up to the last
+    /// `MAX_PARTIAL_REPLAY` results published before subscribing are
+    /// replayed first. The stream closes once the task
+    /// completes, fails or is cancelled; the final result is still available
+    /// through `get_result`.
+    pub fn subscribe_partial(&self, handle: &TaskHandle) -> mpsc::Receiver<PartialResult> {
+        let (sender, receiver) = mpsc::channel();
+        // Unknown or finished tasks get a receiver that is already closed
+        if let Ok(mut partials) = self.partials.lock() {
+            if let Some(channel) = partials.get_mut(&handle.id) {
+                for partial in &channel.history {
+                    let _ = sender.send(partial.clone());
+                }
+                channel.subscribers.push(sender);
+            }
+        }
+        receiver
+    }
+
+    fn partial_sink(partials: Arc<Mutex<HashMap<Uuid, PartialChannel>>>, task_id: Uuid) -> PartialResultSink {
+        Arc::new(move |data| {
+            let Ok(mut partials) = partials.lock() else {
+                return;
+            };
+            if let Some(channel) = partials.get_mut(&task_id) {
+                let partial = PartialResult {
+                    task_id,
+                    sequence: channel.published,
+                    data,
+                };
+                channel.published += 1;
+                channel.subscribers.retain(|s| s.send(partial.clone()).is_ok());
+                if channel.history.len() == MAX_PARTIAL_REPLAY {
+                    channel.history.pop_front();
+                }
+                channel.history.push_back(partial);
+            }
+        })
+    }
+
     pub async fn cancel_task(&self, handle: &TaskHandle) -> Result<()> {
         let mut tasks = self.tasks.write().await;
         let mut results = self.results.write().await;
@@ -182,6 +279,9 @@ impl ComputeEngine {
                     stats.active_tasks -= 1;
                 }
                 stats.failed_tasks += 1;
+
+                // Close any partial result streams
+                self.partials.lock()?.remove(&handle.id);
             }
             Ok(())
         } else {
@@ -199,7 +299,12 @@ impl ComputeEngine {
         stats
     }
 
-    async fn execute_task(task: ComputeTask, thread_pool: Arc<ThreadPool>) -> Result<ComputeResult> {
+    async fn execute_task(
+        task: ComputeTask,
+        thread_pool: Arc<ThreadPool>,
+        partial_sink: PartialResultSink,
+        deadline: Instant,
+    ) -> Result<ComputeResult> {
         let start_time = Instant::now();
 
         // Extract and convert graph parameter
@@ -256,8 +361,23 @@ impl ComputeEngine {
                     .and_then(|v| serde_json::from_value(v.clone()).ok())
                     .ok_or_else(|| Error::computation("Missing target node".to_string()))?;
 
-                let algorithm = PathAnalysis::new(path_type.into(), params);
-                algorithm.execute((graph, source, target)).await?
+                let algorithm = PathAnalysis::new(path_type.into(), params)
+                    .with_partial_sink(partial_sink)
+                    .with_deadline(deadline);
+                let mut analysis_result = algorithm.execute((graph, source, target)).await?;
+
+                // A search stopped at the deadline hands back the paths it
+                // found so far
+                if let Some(message) = analysis_result.remove("timeout") {
+                    return Ok(ComputeResult::timeout(
+                        task.id,
+                        message.as_str().unwrap_or("Task timed out").to_string(),
+                        Some(serde_json::to_value(&analysis_result)?),
+                        start_time.elapsed(),
+                        0,
+                    ));
+                }
+                analysis_result
             }
 
             AnalysisType::Custom(ref name) => {
@@ -285,4 +405,139 @@ fn get_current_memory_usage(sys: &System) -> usize {
     } else {
         0
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::{AnalysisConfig, AnalysisConstraints, PathType};
+
+    /// All-paths task on a complete directed graph of `size` nodes
+    fn all_paths_task(size: usize, timeout: Duration) -> ComputeTask {
+        let nodes: Vec<NodeId> = (0..size).map(|_| Uuid::new_v4()).collect();
+        let mut graph: Graph = HashMap::new();
+        for &source in &nodes {
+            for &target in &nodes {
+                if source != target {
+                    graph.entry(source).or_default().push((target, 1.0));
+                }
+            }
+        }
+
+        let mut parameters = HashMap::new();
+        parameters.insert("graph".to_string(), serde_json::to_value(&graph).unwrap());
+        parameters.insert("source".to_string(), serde_json::to_value(nodes[0]).unwrap());
+        parameters.insert("target".to_string(), serde_json::to_value(nodes[size - 1]).unwrap());
+
+        ComputeTask::new(AnalysisConfig {
+            analysis_type: AnalysisType::Path(PathType::AllPaths),
+            parameters,
+            constraints: AnalysisConstraints {
+                max_iterations: None,
+                convergence_threshold: None,
+                max_memory: None,
+            },
+            timeout,
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn all_paths_streams_partials_before_completing() {
+        let engine = ComputeEngine::new(ComputeConfig::default()).unwrap();
+        // Millions of paths, so the search is still running at subscription
+        // and only the deadline ends it
+        let handle = engine.submit_task(all_paths_task(12, Duration::from_millis(300))).await.unwrap();
+
+        let receiver = engine.subscribe_partial(&handle);
+        let (first, receiver) = tokio::task::spawn_blocking(move || {
+            let first = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+            (first, receiver)
+        })
+        .await
+        .unwrap();
+        assert!(engine.get_result(&handle).await.is_err());
+
+        // The stream only closes once the final result is stored
+        let last = tokio::task::spawn_blocking(move || {
+            receiver.iter().try_fold(first.sequence, |previous, partial| {
+                (partial.sequence == previous + 1).then_some(partial.sequence)
+            })
+        })
+        .await
+        .unwrap()
+        .expect("partials arrive in sequence");
+
+        let result = engine.get_result(&handle).await.unwrap();
+        assert!(result.timed_out);
+        let paths = result.result.unwrap()["paths"].as_array().unwrap().len();
+        assert!(paths > 1);
+        assert_eq!(last + 1, paths);
+
+        // Finished tasks hand out closed streams
+        assert!(engine.subscribe_partial(&handle).recv().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn cancel_closes_partial_stream() {
+        let engine = ComputeEngine::new(ComputeConfig::default()).unwrap();
+        // Far too many paths to finish; the timeout ends the search once the
+        // test is done with it
+        let handle = engine.submit_task(all_paths_task(12, Duration::from_secs(2))).await.unwrap();
+
+        let receiver = engine.subscribe_partial(&handle);
+        let receiver = tokio::task::spawn_blocking(move || {
+            receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+            receiver
+        })
+        .await
+        .unwrap();
+
+        engine.cancel_task(&handle).await.unwrap();
+        let drained = tokio::task::spawn_blocking(move || {
+            while receiver.recv_timeout(Duration::from_secs(5)).is_ok() {}
+            receiver.try_recv()
+        })
+        .await
+        .unwrap();
+        assert_eq!(drained.unwrap_err(), mpsc::TryRecvError::Disconnected);
+    }
+
+    #[test]
+    fn replay_keeps_only_recent_partials() {
+        let engine = ComputeEngine::new(ComputeConfig::default()).unwrap();
+        let task = all_paths_task(2, Duration::from_secs(1));
+        let handle = TaskHandle::new(&task);
+        engine.partials.lock().unwrap().insert(task.id, PartialChannel::default());
+
+        let sink = ComputeEngine::partial_sink(Arc::clone(&engine.partials), task.id);
+        for i in 0..MAX_PARTIAL_REPLAY + 10 {
+            sink(serde_json::json!(i));
+        }
+
+        let replayed: Vec<usize> = engine.subscribe_partial(&handle)
+            .try_iter()
+            .map(|p| p.sequence)
+            .collect();
+        let expected: Vec<usize> = (10..MAX_PARTIAL_REPLAY + 10).collect();
+        assert_eq!(replayed, expected);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn all_paths_timeout_keeps_found_paths() {
+        let engine = ComputeEngine::new(ComputeConfig::default()).unwrap();
+        // Billions of paths; the search can only stop at the deadline
+        let handle = engine.submit_task(all_paths_task(14, Duration::from_millis(10))).await.unwrap();
+
+        let result = loop {
+            match engine.get_result(&handle).await {
+                Ok(result) => break result,
+                Err(_) => tokio::time::sleep(Duration::from_millis(5)).await,
+            }
+        };
+
+        assert!(result.timed_out);
+        assert_eq!(result.status, TaskStatus::TimedOut);
+        assert!(result.error.is_some());
+        let paths = result.result.unwrap()["paths"].as_array().unwrap().len();
+        assert!(paths >= 1);
+    }
+}
@@ -9,7 +9,7 @@ mod task;
 pub mod algorithms;
 
 pub use engine::ComputeEngine;
-pub use task::{ComputeTask, TaskHandle, ComputeResult};
+pub use task::{ComputeTask, TaskHandle, ComputeResult, PartialResult, TaskStatus};
 pub use algorithms::{AnalysisAlgorithm, CentralityAnalysis, CommunityDetection, PathAnalysis};
 
 #[derive(Debug, Clone)]
@@ -36,6 +36,18 @@ pub struct ComputeResult {
     #[serde(with = "serde_duration")]
     pub computation_time: Duration,
     pub memory_used: usize,
+    /// Set when the task hit its timeout; `result` then holds whatever the
+    /// algorithm had produced when it stopped, if it could hand anything back
+    #[serde(default)]
+    pub timed_out: bool,
+}
+
+/// Intermediate result published by an algorithm while its task is running
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialResult {
+    pub task_id: Uuid,
+    pub sequence: usize,
+    pub data: serde_json::Value,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -73,7 +85,7 @@ impl ComputeTask {
     }
 
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        if timeout.as_secs() == 0 {
+        if timeout.is_zero() {
             self.timeout = Duration::from_secs(3600); // Default to 1 hour if 0
         } else {
             self.timeout = timeout;
@@ -150,6 +162,7 @@ impl ComputeResult {
             error: None,
             computation_time,
             memory_used,
+            timed_out: false,
         }
     }
 
@@ -161,6 +174,28 @@ impl ComputeResult {
             error: Some(error),
             computation_time,
             memory_used,
+            timed_out: false,
+        }
+    }
+
+    /// Result for a task that exceeded its timeout. `partial` is the
+    /// algorithm's own result up to the point it stopped, in the same shape
+    /// as a completed run, or `None` when it had nothing to hand back.
+    pub fn timeout(
+        task_id: Uuid,
+        error: String,
+        partial: Option<serde_json::Value>,
+        computation_time: Duration,
+        memory_used: usize,
+    ) -> Self {
+        Self {
+            task_id,
+            status: TaskStatus::TimedOut,
+            result: partial,
+            error: Some(error),
+            computation_time,
+            memory_used,
+            timed_out: true,
         }
     }
 }
@@ -36,12 +36,7 @@ impl SystemExt for System {
     }
 
     fn component_types(&self) -> Vec<String> {
-        let mut types: Vec<_> = self.components.values()
-            .map(|c| c.type_name().to_string())
-            .collect();
-        types.sort();
-        types.dedup();
-        types
+        self.summary().type_breakdown.keys().cloned().collect()
     }
 
     fn weight_range(&self) -> Option<(f32, f32)> {
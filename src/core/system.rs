@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
 use uuid::Uuid;
 use chrono::Utc;
 use crate::error::{Error, Result};
-use crate::core::types::{ComponentState, ComponentType, RelationshipType};
+use crate::core::types::{ComponentState, ComponentType, RelationshipType, SystemSummary};
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -15,6 +16,12 @@ pub struct System {
     pub(crate) metadata: HashMap<String, String>,
     pub(crate) created_at: chrono::DateTime<Utc>,
     pub(crate) updated_at: chrono::DateTime<Utc>,
+    /// Lazily computed summary, reset whenever the system is mutated
+    #[serde(skip)]
+    pub(crate) summary: OnceLock<SystemSummary>,
+    /// Lazily built tag -> component ids index, reset alongside the summary
+    #[serde(skip)]
+    pub(crate) tag_index: OnceLock<HashMap<String, HashSet<Uuid>>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +31,8 @@ pub struct Component {
     pub component_type: ComponentType,
     pub properties: HashMap<String, String>,
     pub state: ComponentState,
+    #[serde(default)]
+    pub tags: HashSet<String>,
     pub created_at: chrono::DateTime<Utc>,
     pub updated_at: chrono::DateTime<Utc>,
 }
@@ -63,15 +72,90 @@ impl System {
             metadata: HashMap::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            summary: OnceLock::new(),
+            tag_index: OnceLock::new(),
         }
     }
 
+    /// Mark the system as modified, invalidating the cached summary and
+    /// tag index
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+        self.summary = OnceLock::new();
+        self.tag_index = OnceLock::new();
+    }
+
+    /// Summary of the system's structure. Computed on first access and
+    /// cached until the system is next mutated.
+    pub fn summary(&self) -> &SystemSummary {
+        self.summary.get_or_init(|| self.compute_summary())
+    }
+
+    fn compute_summary(&self) -> SystemSummary {
+        let component_count = self.components.len();
+        let relationship_count = self.relationships.len();
+
+        let possible_pairs = component_count.saturating_mul(component_count.saturating_sub(1));
+        let density = if possible_pairs > 0 {
+            relationship_count as f64 / possible_pairs as f64
+        } else {
+            0.0
+        };
+
+        let mut type_breakdown = std::collections::BTreeMap::new();
+        for component in self.components.values() {
+            *type_breakdown.entry(component.type_name()).or_insert(0) += 1;
+        }
+
+        let connected: HashSet<Uuid> = self.relationships.values()
+            .flat_map(|r| [r.source_id, r.target_id])
+            .collect();
+        let isolated_count = self.components.keys()
+            .filter(|id| !connected.contains(id))
+            .count();
+
+        SystemSummary {
+            component_count,
+            relationship_count,
+            density,
+            type_breakdown,
+            isolated_count,
+        }
+    }
+
+    fn tag_index(&self) -> &HashMap<String, HashSet<Uuid>> {
+        self.tag_index.get_or_init(|| {
+            let mut index: HashMap<String, HashSet<Uuid>> = HashMap::new();
+            for component in self.components.values() {
+                for tag in &component.tags {
+                    index.entry(tag.clone()).or_default().insert(component.id);
+                }
+            }
+            index
+        })
+    }
+
+    /// Components carrying `tag`
+    pub fn components_with_tag(&self, tag: &str) -> Vec<&Component> {
+        self.tag_index()
+            .get(tag)
+            .map(|ids| ids.iter().filter_map(|id| self.components.get(id)).collect())
+            .unwrap_or_default()
+    }
+
+    /// All tags in use, sorted
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self.tag_index().keys().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags
+    }
+
     pub fn add_component(&mut self, component: Component) -> Result<()> {
         if self.components.contains_key(&component.id) {
             return Err(Error::duplicate_component(component.id));
         }
         self.components.insert(component.id, component);
-        self.updated_at = Utc::now();
+        self.touch();
         Ok(())
     }
 
@@ -89,7 +173,7 @@ impl System {
         }
 
         self.relationships.insert(relationship.id, relationship);
-        self.updated_at = Utc::now();
+        self.touch();
         Ok(())
     }
 
@@ -98,6 +182,10 @@ impl System {
     }
 
     pub fn get_component_mut(&mut self, id: &Uuid) -> Option<&mut Component> {
+        // The caller may change the component's type or tags, so drop the
+        // summary and tag index
+        self.summary = OnceLock::new();
+        self.tag_index = OnceLock::new();
         self.components.get_mut(id)
     }
 
@@ -115,7 +203,7 @@ impl System {
         self.relationships.retain(|_, rel| {
             rel.source_id != *id && rel.target_id != *id
         });
-        self.updated_at = Utc::now();
+        self.touch();
         Ok(())
     }
 
@@ -125,10 +213,47 @@ impl System {
         }
         
         self.relationships.remove(id);
-        self.updated_at = Utc::now();
+        self.touch();
         Ok(())
     }
 
+    /// Set each relationship's weight to `f(source value, target value)`,
+    /// using the endpoints' `state.current_value`. Relationships with a
+    /// missing endpoint, or where either value or the result is not finite,
+    /// keep their existing weight. Returns the number of weights written.
+    pub fn compute_weights_from_values(&mut self, f: impl Fn(f64, f64) -> f64) -> usize {
+        let now = Utc::now();
+        let mut updated = 0;
+
+        for relationship in self.relationships.values_mut() {
+            let (Some(source), Some(target)) = (
+                self.components.get(&relationship.source_id),
+                self.components.get(&relationship.target_id),
+            ) else {
+                continue;
+            };
+
+            let (a, b) = (source.state.current_value, target.state.current_value);
+            if !a.is_finite() || !b.is_finite() {
+                continue;
+            }
+
+            let weight = f(a, b);
+            if !weight.is_finite() {
+                continue;
+            }
+
+            relationship.properties.insert("weight".to_string(), weight.to_string());
+            relationship.updated_at = now;
+            updated += 1;
+        }
+
+        if updated > 0 {
+            self.touch();
+        }
+        updated
+    }
+
     pub fn is_empty(&self) -> bool {
         self.components.is_empty() && self.relationships.is_empty()
     }
@@ -204,6 +329,7 @@ impl Component {
             component_type,
             properties: HashMap::new(),
             state: ComponentState::default(),
+            tags: HashSet::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -228,6 +354,35 @@ impl Component {
         &self.properties
     }
 
+    /// Add a tag, returning false if it was already present
+    pub fn add_tag(&mut self, tag: impl Into<String>) -> bool {
+        let added = self.tags.insert(tag.into());
+        if added {
+            self.updated_at = Utc::now();
+        }
+        added
+    }
+
+    /// Remove a tag, returning false if it was not present
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let removed = self.tags.remove(tag);
+        if removed {
+            self.updated_at = Utc::now();
+        }
+        removed
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(tag)
+    }
+
+    /// Tags in sorted order, for stable output
+    pub fn sorted_tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self.tags.iter().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags
+    }
+
     pub fn update_state(&mut self, state: ComponentState) {
         self.state = state;
         self.updated_at = Utc::now();
@@ -254,4 +409,126 @@ impl Relationship {
     pub fn weight(&self) -> Option<f32> {
         self.properties.get("weight").and_then(|w| w.parse().ok())
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system() -> System {
+        System::new("test".to_string(), "test system".to_string())
+    }
+
+    #[test]
+    fn summary_is_cached_until_mutation() {
+        let mut system = system();
+        let a = Component::new("a".to_string(), ComponentType::Node);
+        let b = Component::new("b".to_string(), ComponentType::Resource);
+        let (a_id, b_id) = (a.id, b.id);
+        system.add_component(a).unwrap();
+        system.add_component(b).unwrap();
+        let relationship = Relationship::new(a_id, b_id, RelationshipType::Dependency);
+        let relationship_id = relationship.id;
+        system.add_relationship(relationship).unwrap();
+
+        let first = system.summary() as *const SystemSummary;
+        assert!(std::ptr::eq(first, system.summary()));
+        assert_eq!(system.summary().relationship_count, 1);
+        assert_eq!(system.summary().isolated_count, 0);
+        assert_eq!(system.summary().density, 0.5);
+
+        system.add_component(Component::new("c".to_string(), ComponentType::Node)).unwrap();
+        let summary = system.summary();
+        assert_eq!(summary.component_count, 3);
+        assert_eq!(summary.type_breakdown.get("Node"), Some(&2));
+        assert_eq!(summary.isolated_count, 1);
+
+        system.remove_relationship(&relationship_id).unwrap();
+        let summary = system.summary();
+        assert_eq!(summary.relationship_count, 0);
+        assert_eq!(summary.isolated_count, 3);
+        assert_eq!(summary.density, 0.0);
+    }
+
+    fn component_with_value(name: &str, value: f64) -> Component {
+        let mut component = Component::new(name.to_string(), ComponentType::Node);
+        component.state.current_value = value;
+        component
+    }
+
+    #[test]
+    fn weights_from_endpoint_values() {
+        let mut system = system();
+        let a = component_with_value("a", 3.0);
+        let b = component_with_value("b", 4.0);
+        let zero = component_with_value("zero", 0.0);
+        let gone = component_with_value("gone", 5.0);
+        let (a_id, b_id, zero_id, gone_id) = (a.id, b.id, zero.id, gone.id);
+        for component in [a, b, zero, gone] {
+            system.add_component(component).unwrap();
+        }
+
+        let ab = Relationship::new(a_id, b_id, RelationshipType::Dependency);
+        let a_zero = Relationship::new(a_id, zero_id, RelationshipType::Dependency);
+        let mut orphan = Relationship::new(a_id, gone_id, RelationshipType::Dependency);
+        orphan.properties.insert("weight".to_string(), "7".to_string());
+        let (ab_id, a_zero_id, orphan_id) = (ab.id, a_zero.id, orphan.id);
+        for relationship in [ab, a_zero, orphan] {
+            system.add_relationship(relationship).unwrap();
+        }
+        // Leave the relationship dangling, as after a partial import
+        system.components.remove(&gone_id);
+
+        let weight = |system: &System, id: &Uuid| system.get_relationship(id).unwrap().weight();
+
+        assert_eq!(system.compute_weights_from_values(|x, y| x * y), 2);
+        assert_eq!(weight(&system, &ab_id), Some(12.0));
+        assert_eq!(weight(&system, &a_zero_id), Some(0.0));
+        assert_eq!(weight(&system, &orphan_id), Some(7.0));
+
+        assert_eq!(system.compute_weights_from_values(f64::min), 2);
+        assert_eq!(weight(&system, &ab_id), Some(3.0));
+        assert_eq!(weight(&system, &a_zero_id), Some(0.0));
+        assert_eq!(weight(&system, &orphan_id), Some(7.0));
+
+        // A non-finite result keeps the previous weight
+        assert_eq!(system.compute_weights_from_values(|x, y| x / y), 1);
+        assert_eq!(weight(&system, &ab_id), Some(0.75));
+        assert_eq!(weight(&system, &a_zero_id), Some(0.0));
+    }
+
+    #[test]
+    fn tag_queries_follow_component_edits() {
+        let mut system = system();
+        let mut a = Component::new("a".to_string(), ComponentType::Node);
+        a.add_tag("core");
+        a.add_tag("critical");
+        let mut b = Component::new("b".to_string(), ComponentType::Node);
+        b.add_tag("core");
+        let c = Component::new("c".to_string(), ComponentType::Node);
+        let (a_id, c_id) = (a.id, c.id);
+        for component in [a, b, c] {
+            system.add_component(component).unwrap();
+        }
+
+        assert_eq!(system.components_with_tag("core").len(), 2);
+        assert_eq!(system.components_with_tag("critical")[0].id, a_id);
+        assert!(system.components_with_tag("missing").is_empty());
+        assert_eq!(system.tags(), vec!["core", "critical"]);
+
+        // Tagging through get_component_mut must drop the cached index
+        assert!(system.get_component_mut(&c_id).unwrap().add_tag("critical"));
+        assert_eq!(system.components_with_tag("critical").len(), 2);
+        assert!(system.get_component_mut(&a_id).unwrap().remove_tag("core"));
+        assert_eq!(system.components_with_tag("core").len(), 1);
+    }
+
+    #[test]
+    fn components_without_tags_deserialize() {
+        let component = Component::new("old".to_string(), ComponentType::Node);
+        let mut value = serde_json::to_value(&component).unwrap();
+        value.as_object_mut().unwrap().remove("tags");
+
+        let component: Component = serde_json::from_value(value).unwrap();
+        assert!(component.tags.is_empty());
+    }
+}
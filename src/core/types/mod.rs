@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use chrono::{DateTime, Utc};
 use serde::{Serialize, Deserialize};
 
@@ -121,6 +121,19 @@ impl SystemMetrics {
     }
 }
 
+/// Structural overview of a system, cached by `System::summary`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemSummary {
+    pub component_count: usize,
+    pub relationship_count: usize,
+    /// Relationships as a fraction of all possible directed pairs
+    pub density: f64,
+    /// Component count per type name
+    pub type_breakdown: BTreeMap<String, usize>,
+    /// Components with no incoming or outgoing relationships
+    pub isolated_count: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Complexity {
     Constant,      // O(1)
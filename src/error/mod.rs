@@ -19,6 +19,7 @@ pub enum Error {
     Runtime(String),
     Storage(String),
     LockPoisoned(String),
+    Timeout(String),
 }
 
 impl Error {
@@ -73,6 +74,10 @@ impl Error {
     pub fn lock_poisoned<T: ToString>(msg: T) -> Self {
         Error::LockPoisoned(msg.to_string())
     }
+
+    pub fn timeout<T: ToString>(msg: T) -> Self {
+        Error::Timeout(msg.to_string())
+    }
 }
 
 impl fmt::Display for Error {
@@ -92,6 +97,7 @@ impl fmt::Display for Error {
             Error::Runtime(msg) => write!(f, "Runtime error: {}", msg),
             Error::Storage(msg) => write!(f, "Storage error: {}", msg),
             Error::LockPoisoned(msg) => write!(f, "Lock poisoned: {}", msg),
+            Error::Timeout(msg) => write!(f, "Timeout: {}", msg),
         }
     }
 }
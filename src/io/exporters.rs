@@ -41,7 +41,7 @@ impl CSVExporter {
             .from_writer(vec![]);
 
         // Write components
-        wtr.write_record(&["id", "name", "type", "created_at", "properties"])?;
+        wtr.write_record(&["id", "name", "type", "created_at", "properties", "tags"])?;
         for component in system.components.values() {
             wtr.write_record(&[
                 component.id.to_string(),
@@ -49,6 +49,7 @@ impl CSVExporter {
                 format!("{:?}", component.component_type),
                 component.created_at.to_rfc3339(),
                 serde_json::to_string(&component.properties)?,
+                serde_json::to_string(&component.sorted_tags())?,
             ])?;
         }
 
@@ -128,6 +129,7 @@ impl SystemExporter for GraphMLExporter {
         output.push_str(r#"  <key id="name" for="node" attr.name="name" attr.type="string"/>
   <key id="type" for="node" attr.name="type" attr.type="string"/>
   <key id="properties" for="node" attr.name="properties" attr.type="string"/>
+  <key id="tags" for="node" attr.name="tags" attr.type="string"/>
   <key id="type" for="edge" attr.name="type" attr.type="string"/>
   <key id="properties" for="edge" attr.name="properties" attr.type="string"/>
 "#);
@@ -142,12 +144,14 @@ impl SystemExporter for GraphMLExporter {
       <data key="name">{}</data>
       <data key="type">{:?}</data>
       <data key="properties">{}</data>
+      <data key="tags">{}</data>
     </node>
 "#,
                 component.id,
                 component.name,
                 component.component_type,
-                serde_json::to_string(&component.properties)?
+                serde_json::to_string(&component.properties)?,
+                serde_json::to_string(&component.sorted_tags())?
             ));
         }
 
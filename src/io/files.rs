@@ -2,37 +2,204 @@ use std::path::{Path, PathBuf};
 use tokio::fs;
 use chrono::Utc;
 use uuid::Uuid;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use zip;
 use csv;
 use std::io::Write;
+use serde::Serialize;
 
 use crate::core::{System, Component, Relationship};
 use crate::core::types::ComponentState;
 use crate::error::{Error, Result};
-use super::{ExportFormat, FileConfig, ImportFormat};
+use super::{ChunkManifest, ChunkPart, ExportFormat, FileConfig, ImportFormat};
+
+/// Matches the historical `{name}-{timestamp}.{ext}` file names
+pub(crate) const DEFAULT_FILENAME_TEMPLATE: &str = "{name}-{date}";
 
 pub struct FileManager {
     base_path: PathBuf,
     temp_dir: PathBuf,
     backup_retention: std::time::Duration,
     max_backup_size: usize,
+    max_records_per_file: usize,
+    export_dir: Option<PathBuf>,
+    filename_template: String,
+}
+
+/// Part of a chunked export being filled; serializes like `ChunkPart`
+/// without cloning the records
+#[derive(Default, Serialize)]
+struct PendingPart<'a> {
+    index: usize,
+    components: Vec<&'a Component>,
+    relationships: Vec<&'a Relationship>,
+}
+
+impl PendingPart<'_> {
+    fn len(&self) -> usize {
+        self.components.len() + self.relationships.len()
+    }
+}
+
+/// Values substituted into a filename template
+struct TemplateValues<'a> {
+    name: &'a str,
+    id: &'a str,
+    date: &'a str,
+    count: usize,
 }
 
 impl FileManager {
-    pub fn new(config: FileConfig) -> Self {
-        Self {
+    pub fn new(config: FileConfig) -> Result<Self> {
+        Self::validate_filename_template(&config.filename_template)?;
+        if let Some(dir) = &config.export_dir {
+            Self::prepare_export_dir(dir)?;
+        }
+
+        Ok(Self {
             base_path: config.base_path,
             temp_dir: config.temp_dir,
             backup_retention: config.backup_retention,
             max_backup_size: config.max_backup_size,
+            max_records_per_file: config.max_records_per_file,
+            export_dir: config.export_dir,
+            filename_template: config.filename_template,
+        })
+    }
+
+    /// Write saved systems to `dir`, creating it if needed. Fails if the
+    /// directory cannot be written to.
+    pub async fn set_export_dir(&mut self, dir: PathBuf) -> Result<()> {
+        Self::prepare_export_dir(&dir)?;
+        self.export_dir = Some(dir);
+        Ok(())
+    }
+
+    /// Create `dir` if needed and check that files can be written to it
+    fn prepare_export_dir(dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            Error::io(format!("Cannot create export directory {}: {}", dir.display(), e))
+        })?;
+
+        let probe = dir.join(format!(".write-test-{}", Uuid::new_v4()));
+        std::fs::write(&probe, b"").map_err(|e| {
+            Error::io(format!("Export directory {} is not writable: {}", dir.display(), e))
+        })?;
+        std::fs::remove_file(&probe)?;
+        Ok(())
+    }
+
+    pub fn set_filename_template(&mut self, template: impl Into<String>) -> Result<()> {
+        let template = template.into();
+        Self::validate_filename_template(&template)?;
+        self.filename_template = template;
+        Ok(())
+    }
+
+    /// Check that `template` only uses known tokens and yields a plain file name
+    pub fn validate_filename_template(template: &str) -> Result<()> {
+        let sample = TemplateValues {
+            name: "system",
+            id: "id",
+            date: "date",
+            count: 1,
+        };
+        Self::render_filename_template(template, &sample).map(|_| ())
+    }
+
+    fn render_filename_template(template: &str, values: &TemplateValues) -> Result<String> {
+        if template.contains(['/', '\\']) {
+            return Err(Error::configuration(format!(
+                "Filename template '{}' must not contain path separators",
+                template
+            )));
+        }
+
+        let mut output = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest[start..].starts_with('}') {
+                return Err(Error::configuration(format!(
+                    "Unmatched '}}' in filename template '{}'",
+                    template
+                )));
+            }
+            output.push_str(&rest[..start]);
+
+            let after = &rest[start + 1..];
+            let end = after.find('}').ok_or_else(|| {
+                Error::configuration(format!("Unclosed '{{' in filename template '{}'", template))
+            })?;
+            match &after[..end] {
+                "name" => output.push_str(values.name),
+                "id" => output.push_str(values.id),
+                "date" => output.push_str(values.date),
+                "count" => output.push_str(&values.count.to_string()),
+                token => {
+                    return Err(Error::configuration(format!(
+                        "Unknown token '{{{}}}' in filename template '{}'; expected {{name}}, {{id}}, {{date}} or {{count}}",
+                        token, template
+                    )));
+                }
+            }
+            rest = &after[end + 1..];
+        }
+        output.push_str(rest);
+
+        if output.trim().is_empty() || output == "." || output == ".." {
+            return Err(Error::configuration(format!(
+                "Filename template '{}' produces an empty file name",
+                template
+            )));
+        }
+        Ok(output)
+    }
+
+    /// File name stem for a new export of `system` in `dir`, such that
+    /// `{stem}.{extension}` can be written. `{count}` becomes the lowest
+    /// number from 1 that does not collide with an existing file.
+    async fn export_stem(&self, dir: &Path, system: &System, extension: &str) -> Result<String> {
+        let name = Self::sanitize_name(&system.name);
+        let id = system.id.to_string();
+        let date = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+        let uses_count = self.filename_template.contains("{count}");
+
+        let mut count = 1;
+        loop {
+            let values = TemplateValues {
+                name: &name,
+                id: &id,
+                date: &date,
+                count,
+            };
+            let stem = Self::render_filename_template(&self.filename_template, &values)?;
+            if !uses_count || !fs::try_exists(dir.join(format!("{}.{}", stem, extension))).await? {
+                return Ok(stem);
+            }
+            count += 1;
+        }
+    }
+
+    /// System name as used for `{name}`: lowercased and dashed, with
+    /// anything that could leave the export directory replaced
+    fn sanitize_name(name: &str) -> String {
+        name.to_lowercase()
+            .replace([' ', '/', '\\'], "-")
+            .replace("..", "-")
+    }
+
+    /// Directory exports of `system` are written to
+    fn export_dir_for(&self, system: &System) -> PathBuf {
+        match &self.export_dir {
+            Some(dir) => dir.clone(),
+            None => self.base_path.join(system.id.to_string()),
         }
     }
 
     pub async fn save_system(&self, system: &System, format: ExportFormat) -> Result<PathBuf> {
-        // Create system directory if it doesn't exist
-        let system_dir = self.base_path.join(system.id.to_string());
-        fs::create_dir_all(&system_dir).await?;
+        // Create export directory if it doesn't exist
+        let export_dir = self.export_dir_for(system);
+        fs::create_dir_all(&export_dir).await?;
 
         // Generate file path
         let extension = match format {
@@ -41,13 +208,8 @@ impl FileManager {
             ExportFormat::GraphML => "graphml",
             ExportFormat::Custom(ref ext) => ext,
         };
-        let filename = format!(
-            "{}-{}.{}",
-            system.name.to_lowercase().replace(' ', "-"),
-            Utc::now().format("%Y%m%d-%H%M%S"),
-            extension
-        );
-        let file_path = system_dir.join(filename);
+        let stem = self.export_stem(&export_dir, system, extension).await?;
+        let file_path = export_dir.join(format!("{}.{}", stem, extension));
 
         // Export system to file
         let data = self.export_system(system, format).await?;
@@ -67,6 +229,127 @@ impl FileManager {
         self.import_system(&data, format).await
     }
 
+    /// Export a system as numbered JSON part files of at most
+    /// `max_records_per_file` records each, plus a manifest listing the parts,
+    /// named by the filename template. Returns the manifest path.
+    pub async fn save_system_chunked(&self, system: &System) -> Result<PathBuf> {
+        if self.max_records_per_file == 0 {
+            return Err(Error::configuration("max_records_per_file must be greater than 0"));
+        }
+
+        let system_dir = self.export_dir_for(system);
+        fs::create_dir_all(&system_dir).await?;
+        // Name the parts after the manifest so `{count}` numbers both alike
+        let stem = self.export_stem(&system_dir, system, "manifest.json").await?;
+
+        // Components fill the first parts so reassembly can add every
+        // component before any relationship that refers to it. Each part is
+        // written as soon as it is full, so only one is held at a time.
+        let mut part = PendingPart::default();
+        let mut part_names = Vec::new();
+        for component in system.components.values() {
+            part.components.push(component);
+            if part.len() == self.max_records_per_file {
+                Self::write_part(&system_dir, &stem, &mut part, &mut part_names).await?;
+            }
+        }
+        for relationship in system.relationships.values() {
+            part.relationships.push(relationship);
+            if part.len() == self.max_records_per_file {
+                Self::write_part(&system_dir, &stem, &mut part, &mut part_names).await?;
+            }
+        }
+        // An empty system still gets one (empty) part
+        if part.len() > 0 || part_names.is_empty() {
+            Self::write_part(&system_dir, &stem, &mut part, &mut part_names).await?;
+        }
+
+        let manifest = ChunkManifest {
+            system_id: system.id,
+            name: system.name.clone(),
+            description: system.description.clone(),
+            metadata: system.metadata.clone(),
+            created_at: system.created_at,
+            updated_at: system.updated_at,
+            component_count: system.components.len(),
+            relationship_count: system.relationships.len(),
+            max_records_per_file: self.max_records_per_file,
+            parts: part_names,
+            version: crate::VERSION.to_string(),
+        };
+        let manifest_path = system_dir.join(format!("{}.manifest.json", stem));
+        fs::write(&manifest_path, serde_json::to_vec_pretty(&manifest)?).await?;
+
+        Ok(manifest_path)
+    }
+
+    /// Reassemble a system from a chunked export's manifest and part files
+    pub async fn load_system_chunked(&self, manifest_path: &Path) -> Result<System> {
+        let manifest: ChunkManifest = serde_json::from_slice(&fs::read(manifest_path).await?)?;
+        let part_dir = manifest_path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut components = Vec::with_capacity(manifest.component_count);
+        let mut relationships = Vec::with_capacity(manifest.relationship_count);
+        for part_name in &manifest.parts {
+            // Parts always sit next to the manifest; anything else could
+            // read files outside the export
+            let is_file_name = !part_name.contains('\\')
+                && Path::new(part_name).file_name().is_some_and(|n| n == part_name.as_str());
+            if !is_file_name {
+                return Err(Error::validation(format!(
+                    "Chunk manifest part '{}' is not a plain file name",
+                    part_name
+                )));
+            }
+            let part: ChunkPart = serde_json::from_slice(&fs::read(part_dir.join(part_name)).await?)?;
+            components.extend(part.components);
+            relationships.extend(part.relationships);
+        }
+
+        if components.len() != manifest.component_count
+            || relationships.len() != manifest.relationship_count
+        {
+            return Err(Error::validation(format!(
+                "Chunked export is incomplete: expected {} components and {} relationships, found {} and {}",
+                manifest.component_count,
+                manifest.relationship_count,
+                components.len(),
+                relationships.len()
+            )));
+        }
+
+        let mut system = System::new(manifest.name, manifest.description);
+        system.id = manifest.system_id;
+        system.metadata = manifest.metadata;
+        for component in components {
+            system.add_component(component)?;
+        }
+        for relationship in relationships {
+            system.add_relationship(relationship)?;
+        }
+        system.created_at = manifest.created_at;
+        system.updated_at = manifest.updated_at;
+
+        Ok(system)
+    }
+
+    /// Write `part` as the next part file and start a new, empty part
+    async fn write_part(
+        dir: &Path,
+        stem: &str,
+        part: &mut PendingPart<'_>,
+        part_names: &mut Vec<String>,
+    ) -> Result<()> {
+        let part_name = format!("{}.part{}.json", stem, part.index);
+        fs::write(dir.join(&part_name), serde_json::to_vec(&*part)?).await?;
+        part_names.push(part_name);
+
+        part.index += 1;
+        part.components.clear();
+        part.relationships.clear();
+        Ok(())
+    }
+
     pub async fn create_backup(&self, system: &System) -> Result<PathBuf> {
         // Create backups directory if it doesn't exist
         let backup_dir = self.base_path.join("backups").join(system.id.to_string());
@@ -155,7 +438,7 @@ impl FileManager {
                     .has_headers(true)
                     .from_writer(vec![]);
 
-                wtr.write_record(&["id", "name", "type", "created_at", "properties"])?;
+                wtr.write_record(&["id", "name", "type", "created_at", "properties", "tags"])?;
                 for component in system.components.values() {
                     wtr.write_record(&[
                         component.id.to_string(),
@@ -163,6 +446,7 @@ impl FileManager {
                         format!("{:?}", component.component_type),
                         component.created_at.to_rfc3339(),
                         serde_json::to_string(&component.properties)?,
+                        serde_json::to_string(&component.sorted_tags())?,
                     ])?;
                 }
                 let components_csv = wtr.into_inner()?;
@@ -208,6 +492,7 @@ impl FileManager {
                 output.push_str(r#"  <key id="name" for="node" attr.name="name" attr.type="string"/>
   <key id="type" for="node" attr.name="type" attr.type="string"/>
   <key id="properties" for="node" attr.name="properties" attr.type="string"/>
+  <key id="tags" for="node" attr.name="tags" attr.type="string"/>
   <key id="weight" for="edge" attr.name="weight" attr.type="double"/>
   <key id="type" for="edge" attr.name="type" attr.type="string"/>
   <key id="properties" for="edge" attr.name="properties" attr.type="string"/>
@@ -223,12 +508,14 @@ impl FileManager {
       <data key="name">{}</data>
       <data key="type">{:?}</data>
       <data key="properties">{}</data>
+      <data key="tags">{}</data>
     </node>
 "#,
                         component.id,
                         component.name,
                         component.component_type,
-                        serde_json::to_string(&component.properties)?
+                        serde_json::to_string(&component.properties)?,
+                        serde_json::to_string(&component.sorted_tags())?
                     ));
                 }
 
@@ -285,6 +572,11 @@ impl FileManager {
                             component_type: serde_json::from_str(&record[2])?,
                             state: ComponentState::default(),
                             properties: serde_json::from_str(&record[4])?,
+                            // Archives written before tags were exported lack the column
+                            tags: match record.get(5) {
+                                Some(tags) => serde_json::from_str(tags)?,
+                                None => HashSet::new(),
+                            },
                             created_at: now,
                             updated_at: now,
                         };
@@ -325,6 +617,8 @@ impl FileManager {
                     components,
                     relationships,
                     metadata: HashMap::new(),
+                    summary: Default::default(),
+                    tag_index: Default::default(),
                 })
             }
             ImportFormat::GraphML => {
@@ -352,4 +646,166 @@ impl FileManager {
             None => Err(Error::validation("File has no extension")),
         }
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{ComponentType, RelationshipType};
+
+    /// File manager writing under a fresh directory in the system temp dir
+    fn file_manager(max_records_per_file: usize) -> (FileManager, PathBuf) {
+        let base_path = std::env::temp_dir().join(format!("csa-files-test-{}", Uuid::new_v4()));
+        let config = FileConfig {
+            base_path: base_path.clone(),
+            max_records_per_file,
+            ..FileConfig::default()
+        };
+        (FileManager::new(config).unwrap(), base_path)
+    }
+
+    fn sample_system() -> System {
+        let mut system = System::new("Chunk Test".to_string(), "chunked".to_string());
+        let ids: Vec<Uuid> = (0..5)
+            .map(|i| {
+                let component = Component::new(format!("c{}", i), ComponentType::Node);
+                let id = component.id;
+                system.add_component(component).unwrap();
+                id
+            })
+            .collect();
+        for pair in ids.windows(2) {
+            system.add_relationship(Relationship::new(pair[0], pair[1], RelationshipType::Dependency)).unwrap();
+        }
+        system
+    }
+
+    #[tokio::test]
+    async fn chunked_export_round_trips() {
+        let (manager, base_path) = file_manager(3);
+        let system = sample_system();
+
+        let manifest_path = manager.save_system_chunked(&system).await.unwrap();
+        let manifest: ChunkManifest = serde_json::from_slice(&fs::read(&manifest_path).await.unwrap()).unwrap();
+        // 5 components and 4 relationships, 3 records per part
+        assert_eq!(manifest.parts.len(), 3);
+
+        let loaded = manager.load_system_chunked(&manifest_path).await.unwrap();
+        assert_eq!(loaded.id, system.id);
+        assert_eq!(loaded.components.len(), 5);
+        assert_eq!(loaded.relationships.len(), 4);
+        assert_eq!(loaded.updated_at, system.updated_at);
+
+        fs::remove_dir_all(base_path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn chunked_import_rejects_paths_in_manifest() {
+        let (manager, base_path) = file_manager(3);
+        let manifest_path = manager.save_system_chunked(&sample_system()).await.unwrap();
+
+        let mut manifest: ChunkManifest = serde_json::from_slice(&fs::read(&manifest_path).await.unwrap()).unwrap();
+        manifest.parts[0] = format!("../{}", manifest.parts[0]);
+        fs::write(&manifest_path, serde_json::to_vec(&manifest).unwrap()).await.unwrap();
+
+        let error = manager.load_system_chunked(&manifest_path).await.unwrap_err();
+        assert!(matches!(error, Error::Validation(_)));
+
+        fs::remove_dir_all(base_path).await.unwrap();
+    }
+
+    #[test]
+    fn filename_template_tokens() {
+        let values = TemplateValues {
+            name: "my-system",
+            id: "1234",
+            date: "20261016-120000",
+            count: 3,
+        };
+        let render = |template: &str| FileManager::render_filename_template(template, &values);
+
+        assert_eq!(
+            render("{name}_{id}_{date}_v{count}").unwrap(),
+            "my-system_1234_20261016-120000_v3"
+        );
+        assert!(matches!(render("{name}-{version}"), Err(Error::Configuration(_))));
+        assert!(matches!(render("exports/{name}"), Err(Error::Configuration(_))));
+        assert!(matches!(render("{name"), Err(Error::Configuration(_))));
+    }
+
+    #[test]
+    fn invalid_template_is_rejected_up_front() {
+        let config = FileConfig {
+            filename_template: "{name}/{date}".to_string(),
+            ..FileConfig::default()
+        };
+        assert!(FileManager::new(config).is_err());
+    }
+
+    #[test]
+    fn system_name_cannot_escape_export_dir() {
+        assert_eq!(FileManager::sanitize_name("../Etc/Passwd"), "--etc-passwd");
+        assert_eq!(FileManager::sanitize_name("Power Grid"), "power-grid");
+        assert_eq!(FileManager::sanitize_name("a\\..\\b"), "a---b");
+    }
+
+    #[tokio::test]
+    async fn save_system_names_file_from_template() {
+        let (mut manager, base_path) = file_manager(4);
+        manager.set_filename_template("{name}_{id}-v{count}").unwrap();
+        let system = sample_system();
+        let stem = format!("chunk-test_{}", system.id);
+
+        let first = manager.save_system(&system, ExportFormat::JSON).await.unwrap();
+        let second = manager.save_system(&system, ExportFormat::JSON).await.unwrap();
+        let dir = base_path.join(system.id.to_string());
+        assert_eq!(first, dir.join(format!("{}-v1.json", stem)));
+        assert_eq!(second, dir.join(format!("{}-v2.json", stem)));
+
+        let loaded = manager.load_system(&first).await.unwrap();
+        assert_eq!(loaded.id, system.id);
+
+        fs::remove_dir_all(base_path).await.unwrap();
+    }
+
+    #[test]
+    fn new_checks_configured_export_dir() {
+        let base_path = std::env::temp_dir().join(format!("csa-files-test-{}", Uuid::new_v4()));
+        let export_dir = base_path.join("exports");
+        let config = FileConfig {
+            base_path: base_path.clone(),
+            export_dir: Some(export_dir.clone()),
+            ..FileConfig::default()
+        };
+        FileManager::new(config).unwrap();
+        assert!(export_dir.is_dir());
+
+        // A file where the directory should be cannot be created or written to
+        let blocked = base_path.join("blocked");
+        std::fs::write(&blocked, b"").unwrap();
+        let config = FileConfig {
+            base_path: base_path.clone(),
+            export_dir: Some(blocked.join("exports")),
+            ..FileConfig::default()
+        };
+        assert!(matches!(FileManager::new(config), Err(Error::IO(_))));
+
+        std::fs::remove_dir_all(base_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn chunked_export_uses_export_dir_and_template() {
+        let (mut manager, base_path) = file_manager(4);
+        let export_dir = base_path.join("exports");
+        manager.set_export_dir(export_dir.clone()).await.unwrap();
+        manager.set_filename_template("{name}-{count}").unwrap();
+        let system = sample_system();
+
+        let first = manager.save_system_chunked(&system).await.unwrap();
+        let second = manager.save_system_chunked(&system).await.unwrap();
+        assert_eq!(first, export_dir.join("chunk-test-1.manifest.json"));
+        assert_eq!(second, export_dir.join("chunk-test-2.manifest.json"));
+        assert!(fs::try_exists(export_dir.join("chunk-test-2.part0.json")).await.unwrap());
+
+        fs::remove_dir_all(base_path).await.unwrap();
+    }
+}
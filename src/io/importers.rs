@@ -217,6 +217,13 @@ impl SystemImporter for JSONImporter {
                     }
                 }
 
+                // Add tags
+                if let Some(tags) = component_value.get("tags").and_then(|t| t.as_array()) {
+                    for tag in tags.iter().filter_map(|t| t.as_str()) {
+                        component.add_tag(tag);
+                    }
+                }
+
                 system.add_component(component)?;
             }
         }
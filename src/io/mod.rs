@@ -5,7 +5,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use crate::error::{Error, Result};
-use crate::core::System;
+use crate::core::{System, Component, Relationship};
 
 mod exporters;
 mod importers;
@@ -36,6 +36,15 @@ pub struct FileConfig {
     pub temp_dir: PathBuf,
     pub backup_retention: std::time::Duration,
     pub max_backup_size: usize,
+    /// Maximum components plus relationships written to each part file of a
+    /// chunked export
+    pub max_records_per_file: usize,
+    /// Directory saved systems are written to; `None` uses a per-system
+    /// directory under `base_path`
+    pub export_dir: Option<PathBuf>,
+    /// Saved file name without extension. Supports the `{name}`, `{id}`,
+    /// `{date}` and `{count}` tokens.
+    pub filename_template: String,
 }
 
 impl Default for FileConfig {
@@ -45,6 +54,9 @@ impl Default for FileConfig {
             temp_dir: std::env::temp_dir().join("csa"),
             backup_retention: std::time::Duration::from_secs(7 * 24 * 3600), // 7 days
             max_backup_size: 1024 * 1024 * 1024, // 1GB
+            max_records_per_file: 50_000,
+            export_dir: None,
+            filename_template: files::DEFAULT_FILENAME_TEMPLATE.to_string(),
         }
     }
 }
@@ -68,12 +80,39 @@ pub struct ImportMetadata {
     pub version: String,
 }
 
+/// Index of a chunked export, listing its part files in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkManifest {
+    pub system_id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub metadata: std::collections::HashMap<String, String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub component_count: usize,
+    pub relationship_count: usize,
+    pub max_records_per_file: usize,
+    /// Part file names, relative to the manifest's directory
+    pub parts: Vec<String>,
+    pub version: String,
+}
+
+/// One part file of a chunked export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChunkPart {
+    pub index: usize,
+    pub components: Vec<Component>,
+    pub relationships: Vec<Relationship>,
+}
+
 #[async_trait]
 pub trait IOManager: Send + Sync {
     async fn export_system(&self, system: &System, format: ExportFormat) -> Result<Vec<u8>>;
     async fn import_system(&self, data: &[u8], format: ImportFormat) -> Result<System>;
     async fn save_system(&self, system: &System) -> Result<PathBuf>;
     async fn load_system(&self, path: &Path) -> Result<System>;
+    async fn save_system_chunked(&self, system: &System) -> Result<PathBuf>;
+    async fn load_system_chunked(&self, manifest_path: &Path) -> Result<System>;
     async fn create_backup(&self, system: &System) -> Result<PathBuf>;
     async fn restore_backup(&self, backup_path: &Path) -> Result<System>;
     async fn list_backups(&self) -> Result<Vec<PathBuf>>;
@@ -87,7 +126,7 @@ pub struct DefaultIOManager {
 }
 
 impl DefaultIOManager {
-    pub fn new(config: FileConfig) -> Self {
+    pub fn new(config: FileConfig) -> Result<Self> {
         let mut exporters: Vec<Box<dyn SystemExporter>> = Vec::new();
         exporters.push(Box::new(JSONExporter::new()));
         exporters.push(Box::new(CSVExporter::new()));
@@ -96,11 +135,11 @@ impl DefaultIOManager {
         let mut importers: Vec<Box<dyn SystemImporter>> = Vec::new();
         importers.push(Box::new(JSONImporter::new()));
 
-        Self {
+        Ok(Self {
             exporters,
             importers,
-            file_manager: FileManager::new(config),
-        }
+            file_manager: FileManager::new(config)?,
+        })
     }
 
     fn get_exporter(&self, format: ExportFormat) -> Result<&dyn SystemExporter> {
@@ -111,6 +150,14 @@ impl DefaultIOManager {
             .ok_or_else(|| Error::system(format!("Unsupported export format: {:?}", format)))
     }
 
+    pub async fn set_export_dir(&mut self, dir: PathBuf) -> Result<()> {
+        self.file_manager.set_export_dir(dir).await
+    }
+
+    pub fn set_filename_template(&mut self, template: impl Into<String>) -> Result<()> {
+        self.file_manager.set_filename_template(template)
+    }
+
     fn get_importer(&self, format: ImportFormat) -> Result<&dyn SystemImporter> {
         self.importers
             .iter()
@@ -145,6 +192,14 @@ impl IOManager for DefaultIOManager {
         self.file_manager.load_system(path).await
     }
 
+    async fn save_system_chunked(&self, system: &System) -> Result<PathBuf> {
+        self.file_manager.save_system_chunked(system).await
+    }
+
+    async fn load_system_chunked(&self, manifest_path: &Path) -> Result<System> {
+        self.file_manager.load_system_chunked(manifest_path).await
+    }
+
     async fn create_backup(&self, system: &System) -> Result<PathBuf> {
         self.file_manager.create_backup(system).await
     }
@@ -117,6 +117,8 @@ impl StorageManager {
             components: HashMap::new(),
             relationships: HashMap::new(),
             metadata: system_metadata,
+            summary: Default::default(),
+            tag_index: Default::default(),
         };
 
         // Update cache
@@ -8,7 +8,7 @@ use std::sync::Mutex;
 use crate::error::{Error, Result};
 use crate::core::Component;
 
-const SCHEMA_VERSION: u32 = 1;
+const SCHEMA_VERSION: u32 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemMetadata {
@@ -30,6 +30,7 @@ pub struct ComponentMetadata {
     pub created_at: DateTime<Utc>,
     pub modified_at: DateTime<Utc>,
     pub properties: serde_json::Value,
+    pub tags: Vec<String>,
 }
 
 pub struct SQLiteDB {
@@ -73,6 +74,7 @@ impl SQLiteDB {
                 created_at TEXT NOT NULL,
                 modified_at TEXT NOT NULL,
                 properties TEXT NOT NULL,
+                tags TEXT NOT NULL DEFAULT '[]',
                 FOREIGN KEY (system_id) REFERENCES systems(id)
             );
 
@@ -168,8 +170,8 @@ impl SQLiteDB {
     pub fn store_component_metadata(&self, component: &Component) -> Result<()> {
         self.connection.lock().unwrap().execute(
             r#"
-            INSERT OR REPLACE INTO components (id, name, component_type, created_at, modified_at, properties)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            INSERT OR REPLACE INTO components (id, name, component_type, created_at, modified_at, properties, tags)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
             "#,
             params![
                 component.id.as_bytes(),
@@ -178,7 +180,9 @@ impl SQLiteDB {
                 component.created_at.to_rfc3339(),
                 component.updated_at.to_rfc3339(),
                 serde_json::to_string(&component.properties)
-                    .map_err(|e| Error::Storage(format!("Failed to serialize properties: {}", e)))?
+                    .map_err(|e| Error::Storage(format!("Failed to serialize properties: {}", e)))?,
+                serde_json::to_string(&component.sorted_tags())
+                    .map_err(|e| Error::Storage(format!("Failed to serialize tags: {}", e)))?
             ],
         ).map_err(|e| Error::Storage(format!("Failed to store component metadata: {}", e)))?;
 
@@ -188,7 +192,7 @@ impl SQLiteDB {
     pub fn get_component_metadata(&self, id: &Uuid) -> Result<Option<ComponentMetadata>> {
         let result = self.connection.lock().unwrap().query_row(
             r#"
-            SELECT system_id, name, component_type, created_at, modified_at, properties
+            SELECT system_id, name, component_type, created_at, modified_at, properties, tags
             FROM components WHERE id = ?1
             "#,
             params![id.as_bytes()],
@@ -221,6 +225,12 @@ impl SQLiteDB {
                             rusqlite::types::Type::Text,
                             Box::new(e),
                         ))?,
+                    tags: serde_json::from_str(&row.get::<_, String>(6)?)
+                        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(
+                            6,
+                            rusqlite::types::Type::Text,
+                            Box::new(e),
+                        ))?,
                 })
             },
         );
@@ -238,9 +248,11 @@ impl SQLiteDB {
             .map_err(|e| Error::Storage(format!("Failed to start migration transaction: {}", e)))?;
 
         // Run migrations based on version
-        match current_version {
-            1 => {},
-            _ => {}
+        if current_version < 2 {
+            tx.execute(
+                "ALTER TABLE components ADD COLUMN tags TEXT NOT NULL DEFAULT '[]'",
+                [],
+            ).map_err(|e| Error::Storage(format!("Failed to add component tags column: {}", e)))?;
         }
 
         tx.execute(
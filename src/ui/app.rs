@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use crate::compute::ComputeResult;
 use crate::error::Result;
 use super::{AppState, UIConfig, UIEvent, UICommand, CommandResponse, views::ViewManager};
 
@@ -71,6 +72,13 @@ impl App {
         Ok(())
     }
 
+    /// Hand a finished compute task's result to the state and notify the
+    /// views, e.g. with `AnalysisTimedOut` when the results are partial
+    pub fn handle_compute_result(&mut self, result: &ComputeResult) -> Result<()> {
+        let event = self.state.record_compute_result(result)?;
+        self.view_manager.handle_event(&event)
+    }
+
     pub fn handle_command(&self, command: UICommand) -> Result<CommandResponse> {
         self.bridge.handle_command(command)
     }
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::sync::mpsc;
 use serde_json::Value as JsonValue;
 use crate::error::Result;
+use crate::visualization::FocusOrder;
 
 mod app;
 mod state;
@@ -19,7 +20,7 @@ pub enum Theme {
     System,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LayoutType {
     Force,
     Grid,
@@ -32,6 +33,12 @@ pub struct LayoutConfig {
     pub layout_type: LayoutType,
     pub spacing: f32,
     pub padding: f32,
+    /// Minimum distance between node centers enforced after layout;
+    /// `None` disables the de-overlap pass
+    pub min_node_separation: Option<f32>,
+    /// Align dragged nodes and grid layouts to a grid of `grid_cell_size`
+    pub snap_to_grid: bool,
+    pub grid_cell_size: f32,
 }
 
 impl Default for LayoutConfig {
@@ -40,6 +47,45 @@ impl Default for LayoutConfig {
             layout_type: LayoutType::Force,
             spacing: 50.0,
             padding: 20.0,
+            min_node_separation: None,
+            snap_to_grid: false,
+            grid_cell_size: 25.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NavigationConfig {
+    /// Whether Tab/arrow/Enter keys move node focus in the graph view
+    pub enabled: bool,
+    pub focus_order: FocusOrder,
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            focus_order: FocusOrder::Id,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SamplingConfig {
+    /// Render a sampled preview instead of the full graph for large systems
+    pub enabled: bool,
+    /// Components plus relationships above which the preview is used
+    pub threshold: usize,
+    /// Number of highest-degree nodes kept in the preview
+    pub max_nodes: usize,
+}
+
+impl Default for SamplingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 20_000,
+            max_nodes: 2_000,
         }
     }
 }
@@ -49,6 +95,8 @@ pub struct UIConfig {
     pub window_size: (u32, u32),
     pub theme: Theme,
     pub layout: LayoutConfig,
+    pub navigation: NavigationConfig,
+    pub sampling: SamplingConfig,
 }
 
 impl Default for UIConfig {
@@ -57,6 +105,8 @@ impl Default for UIConfig {
             window_size: (1280, 720),
             theme: Theme::System,
             layout: LayoutConfig::default(),
+            navigation: NavigationConfig::default(),
+            sampling: SamplingConfig::default(),
         }
     }
 }
@@ -132,6 +182,12 @@ pub enum UIEvent {
     ViewportChanged,
     AnalysisStarted,
     AnalysisCompleted,
+    /// The analysis hit its timeout; any results are partial
+    AnalysisTimedOut,
+    KeyPressed(String),
+    /// A node was dragged to `(x, y)`; `invert_snap` is set while the snap
+    /// modifier key is held
+    NodeDragged { id: String, x: f32, y: f32, invert_snap: bool },
     Error(String),
 }
 
@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
 use std::sync::{Arc, RwLock};
+use serde_json::Value as JsonValue;
 use crate::core::System;
+use crate::compute::{ComputeResult, TaskStatus};
 use crate::visualization::VisualizationEngine;
 use crate::error::Result;
 
@@ -8,16 +11,24 @@ pub struct AppState {
     visualization: Arc<RwLock<VisualizationEngine>>,
     selected_components: RwLock<Vec<String>>,
     active_analysis: RwLock<Option<String>>,
+    analysis_results: RwLock<Option<super::AnalysisResult>>,
+    /// Whether the stored results are partial because the analysis timed out
+    analysis_timed_out: RwLock<bool>,
     ui_config: RwLock<super::UIConfig>,
 }
 
 impl AppState {
     pub fn new(config: super::UIConfig) -> Self {
+        let mut visualization = VisualizationEngine::new(config.layout.clone());
+        visualization.set_sampling_config(config.sampling.clone());
+
         Self {
             system: Arc::new(RwLock::new(System::default())),
-            visualization: Arc::new(RwLock::new(VisualizationEngine::new(config.layout.clone()))),
+            visualization: Arc::new(RwLock::new(visualization)),
             selected_components: RwLock::new(Vec::new()),
             active_analysis: RwLock::new(None),
+            analysis_results: RwLock::new(None),
+            analysis_timed_out: RwLock::new(false),
             ui_config: RwLock::new(config),
         }
     }
@@ -98,12 +109,157 @@ impl AppState {
     }
 
     pub fn clear_analysis_results(&self) -> Result<()> {
-        // TODO: Implement results clearing
+        *self.analysis_results.write()? = None;
+        *self.analysis_timed_out.write()? = false;
         Ok(())
     }
 
     pub fn get_analysis_results(&self) -> Result<Option<super::AnalysisResult>> {
-        // TODO: Implement results retrieval
-        Ok(None)
+        Ok(self.analysis_results.read()?.clone())
+    }
+
+    /// Whether the current results are partial because the analysis timed out
+    pub fn analysis_timed_out(&self) -> Result<bool> {
+        Ok(*self.analysis_timed_out.read()?)
+    }
+
+    /// Store the outcome of a finished compute task and return the event the
+    /// views should receive. A timed-out task keeps whatever partial result
+    /// its algorithm handed back and yields `AnalysisTimedOut`; a failed one
+    /// leaves the previous results alone and yields `Error`.
+    pub fn record_compute_result(&self, result: &ComputeResult) -> Result<super::UIEvent> {
+        if !matches!(result.status, TaskStatus::Completed | TaskStatus::TimedOut) {
+            let message = result.error.clone()
+                .unwrap_or_else(|| format!("Analysis ended with status {:?}", result.status));
+            return Ok(super::UIEvent::Error(message));
+        }
+
+        *self.analysis_results.write()? = result.result.as_ref().and_then(analysis_result_from_json);
+        *self.analysis_timed_out.write()? = result.timed_out;
+
+        Ok(if result.timed_out {
+            super::UIEvent::AnalysisTimedOut
+        } else {
+            super::UIEvent::AnalysisCompleted
+        })
     }
-} 
\ No newline at end of file
+}
+
+/// Convert an algorithm's JSON output into the form the results panel shows
+fn analysis_result_from_json(value: &JsonValue) -> Option<super::AnalysisResult> {
+    if let Some(paths) = value.get("paths").and_then(JsonValue::as_array) {
+        let paths = paths.iter()
+            .filter_map(|path| path.get("nodes")?.as_array())
+            .map(|nodes| {
+                let nodes: Vec<String> = nodes.iter()
+                    .filter_map(|n| n.as_str().map(str::to_string))
+                    .collect();
+                let start = nodes.first().cloned().unwrap_or_default();
+                let end = nodes.last().cloned().unwrap_or_default();
+                (start, end, nodes)
+            })
+            .collect();
+        return Some(super::AnalysisResult::Paths(paths));
+    }
+
+    if let Some(values) = value.get("centrality_values").and_then(JsonValue::as_object) {
+        let mut scores: Vec<(String, f64)> = values.iter()
+            .filter_map(|(node, score)| Some((node.clone(), score.as_f64()?)))
+            .collect();
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        return Some(super::AnalysisResult::Centrality(scores));
+    }
+
+    if let Some(assignments) = value.get("community_assignments").and_then(JsonValue::as_object) {
+        let mut communities: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        for (node, community) in assignments {
+            if let Some(community) = community.as_u64() {
+                communities.entry(community).or_default().push(node.clone());
+            }
+        }
+        return Some(super::AnalysisResult::Clustering(communities.into_values().collect()));
+    }
+
+    None
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use uuid::Uuid;
+    use crate::ui::{AnalysisResult, UIConfig, UIEvent};
+
+    #[test]
+    fn timed_out_result_is_kept_as_partial() {
+        let state = AppState::new(UIConfig::default());
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let path = serde_json::json!({
+            "nodes": [a.to_string(), b.to_string(), c.to_string()],
+            "weight": 2.0,
+        });
+        let result = ComputeResult::timeout(
+            Uuid::new_v4(),
+            "All-paths search stopped after finding 1 paths".to_string(),
+            Some(serde_json::json!({ "paths": [path] })),
+            Duration::from_millis(10),
+            0,
+        );
+
+        let event = state.record_compute_result(&result).unwrap();
+        assert!(matches!(event, UIEvent::AnalysisTimedOut));
+        assert!(state.analysis_timed_out().unwrap());
+        match state.get_analysis_results().unwrap() {
+            Some(AnalysisResult::Paths(paths)) => {
+                assert_eq!(paths.len(), 1);
+                assert_eq!(paths[0].0, a.to_string());
+                assert_eq!(paths[0].1, c.to_string());
+                assert_eq!(paths[0].2.len(), 3);
+            }
+            other => panic!("expected partial paths, got {:?}", other),
+        }
+
+        state.clear_analysis_results().unwrap();
+        assert!(state.get_analysis_results().unwrap().is_none());
+        assert!(!state.analysis_timed_out().unwrap());
+    }
+
+    #[test]
+    fn timed_out_result_without_partial_has_no_results() {
+        let state = AppState::new(UIConfig::default());
+        let completed = ComputeResult::success(
+            Uuid::new_v4(),
+            serde_json::json!({ "paths": [] }),
+            Duration::from_millis(1),
+            0,
+        );
+        state.record_compute_result(&completed).unwrap();
+
+        // e.g. a centrality task interrupted by the engine's outer timeout
+        let result = ComputeResult::timeout(
+            Uuid::new_v4(),
+            "Task exceeded its 10ms timeout".to_string(),
+            None,
+            Duration::from_millis(10),
+            0,
+        );
+        assert!(matches!(state.record_compute_result(&result).unwrap(), UIEvent::AnalysisTimedOut));
+        assert!(state.analysis_timed_out().unwrap());
+        assert!(state.get_analysis_results().unwrap().is_none());
+    }
+
+    #[test]
+    fn failed_result_keeps_previous_results() {
+        let state = AppState::new(UIConfig::default());
+        let completed = ComputeResult::success(
+            Uuid::new_v4(),
+            serde_json::json!({ "paths": [] }),
+            Duration::from_millis(1),
+            0,
+        );
+        assert!(matches!(state.record_compute_result(&completed).unwrap(), UIEvent::AnalysisCompleted));
+
+        let failed = ComputeResult::failure(Uuid::new_v4(), "boom".to_string(), Duration::ZERO, 0);
+        assert!(matches!(state.record_compute_result(&failed).unwrap(), UIEvent::Error(message) if message == "boom"));
+        assert!(matches!(state.get_analysis_results().unwrap(), Some(AnalysisResult::Paths(_))));
+    }
+}
@@ -4,6 +4,8 @@ use super::View;
 use crate::ui::{AppState, UIEvent, AnalysisConfig, AnalysisResult};
 use crate::ui::widgets::{Button, Slider, Checkbox};
 
+const TIMED_OUT_BANNER: &str = "Results incomplete (timed out)";
+
 pub struct AnalysisView {
     state: Arc<AppState>,
     config_panel: ConfigPanel,
@@ -20,6 +22,8 @@ struct ConfigPanel {
 
 struct ResultsPanel {
     title_button: Button,
+    /// Shown above the results when they are incomplete
+    banner: Option<String>,
     result_text: String,
     export_button: Button,
     clear_button: Button,
@@ -39,6 +43,7 @@ impl AnalysisView {
             },
             results_panel: ResultsPanel {
                 title_button: Button::new("Analysis Results"),
+                banner: None,
                 result_text: String::new(),
                 export_button: Button::new("Export Results"),
                 clear_button: Button::new("Clear Results"),
@@ -146,8 +151,10 @@ impl AnalysisView {
         Ok(config)
     }
 
-    fn update_results_display(&mut self, results: &AnalysisResult) -> Result<()> {
+    fn update_results_display(&mut self, results: &AnalysisResult, timed_out: bool) -> Result<()> {
         self.results_panel.result_text.clear();
+        self.results_panel.banner = timed_out
+            .then(|| TIMED_OUT_BANNER.to_string());
         
         // Format and display results
         match results {
@@ -199,7 +206,16 @@ impl View for AnalysisView {
             UIEvent::AnalysisCompleted => {
                 // Update results when analysis completes
                 if let Ok(Some(results)) = self.state.get_analysis_results() {
-                    self.update_results_display(&results)?;
+                    self.update_results_display(&results, false)?;
+                }
+            }
+            UIEvent::AnalysisTimedOut => {
+                // Show whatever was found before the timeout, flagged as partial
+                if let Ok(Some(results)) = self.state.get_analysis_results() {
+                    self.update_results_display(&results, true)?;
+                } else {
+                    self.results_panel.result_text.clear();
+                    self.results_panel.banner = Some(TIMED_OUT_BANNER.to_string());
                 }
             }
             _ => {}
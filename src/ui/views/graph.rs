@@ -2,14 +2,25 @@ use std::sync::Arc;
 use crate::error::Result;
 use super::View;
 use crate::ui::{AppState, UIEvent};
+use uuid::Uuid;
+use crate::visualization::{Direction, Point};
 
 pub struct GraphView {
     state: Arc<AppState>,
+    sample_indicator: Option<String>,
 }
 
 impl GraphView {
     pub fn new(state: Arc<AppState>) -> Self {
-        Self { state }
+        Self {
+            state,
+            sample_indicator: None,
+        }
+    }
+
+    /// "Showing N of M nodes" while a sampled preview is rendered
+    pub fn sample_indicator(&self) -> Option<&str> {
+        self.sample_indicator.as_deref()
     }
 
     fn handle_graph_update(&mut self) -> Result<()> {
@@ -21,6 +32,7 @@ impl GraphView {
         
         vis.update_graph(&system)?;
         vis.render_frame()?;
+        self.sample_indicator = vis.sample_info().map(|sample| sample.indicator());
         
         Ok(())
     }
@@ -37,6 +49,60 @@ impl GraphView {
         
         Ok(())
     }
+
+    fn handle_drag(&mut self, id: &str, x: f32, y: f32, invert_snap: bool) -> Result<()> {
+        let Ok(id) = Uuid::parse_str(id) else {
+            return Ok(());
+        };
+
+        let vis = self.state.get_visualization();
+        let mut vis = vis.write()?;
+        vis.drag_node(&id, Point::new(x, y), invert_snap)?;
+        vis.render_frame()?;
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &str) -> Result<()> {
+        let navigation = self.state.get_ui_config()?.navigation;
+        if !navigation.enabled {
+            return Ok(());
+        }
+
+        let vis = self.state.get_visualization();
+        let mut vis = vis.write()?;
+
+        match key {
+            "Tab" => {
+                vis.focus_next(navigation.focus_order, false)?;
+            }
+            "Shift+Tab" => {
+                vis.focus_next(navigation.focus_order, true)?;
+            }
+            "ArrowUp" => {
+                vis.focus_direction(Direction::Up)?;
+            }
+            "ArrowDown" => {
+                vis.focus_direction(Direction::Down)?;
+            }
+            "ArrowLeft" => {
+                vis.focus_direction(Direction::Left)?;
+            }
+            "ArrowRight" => {
+                vis.focus_direction(Direction::Right)?;
+            }
+            "Enter" => {
+                if let Some(id) = vis.focused_node() {
+                    drop(vis);
+                    return self.handle_selection(&[id.to_string()]);
+                }
+                return Ok(());
+            }
+            _ => return Ok(()),
+        }
+
+        vis.render_frame()?;
+        Ok(())
+    }
 }
 
 impl View for GraphView {
@@ -55,6 +121,7 @@ impl View for GraphView {
         let vis = self.state.get_visualization();
         let mut vis = vis.write()?;
         vis.render_frame()?;
+        self.sample_indicator = vis.sample_info().map(|sample| sample.indicator());
         Ok(())
     }
 
@@ -62,6 +129,10 @@ impl View for GraphView {
         match event {
             UIEvent::GraphUpdated => self.handle_graph_update()?,
             UIEvent::SelectionChanged(ids) => self.handle_selection(ids)?,
+            UIEvent::KeyPressed(key) => self.handle_key(key)?,
+            UIEvent::NodeDragged { id, x, y, invert_snap } => {
+                self.handle_drag(id, *x, *y, *invert_snap)?
+            }
             _ => {}
         }
         Ok(())
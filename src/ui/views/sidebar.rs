@@ -21,6 +21,7 @@ struct PropertyPanel {
 struct FilterPanel {
     title_button: Button,
     type_filters: Vec<Checkbox>,
+    tag_filters: Vec<Checkbox>,
     weight_range: Slider,
     date_range: (Slider, Slider),
     is_expanded: bool,
@@ -44,6 +45,7 @@ impl SidebarView {
             filter_panel: FilterPanel {
                 title_button: Button::new("Filters"),
                 type_filters: Vec::new(),
+                tag_filters: Vec::new(),
                 weight_range: Slider::new(0.0, 1.0),
                 date_range: (
                     Slider::new(0.0, 100.0),
@@ -95,6 +97,12 @@ impl SidebarView {
             is_editable: false,
         });
 
+        self.property_panel.properties.push(PropertyField {
+            label: "Tags".to_string(),
+            value: component.sorted_tags().join(", "),
+            is_editable: false,
+        });
+
         // Add other component-specific properties
         for (key, value) in component.properties() {
             self.property_panel.properties.push(PropertyField {
@@ -137,15 +145,42 @@ impl SidebarView {
 
         // Update component type filters
         self.filter_panel.type_filters = system
-            .component_types()
-            .iter()
+            .summary()
+            .type_breakdown
+            .keys()
             .map(|t| {
-                let mut checkbox = Checkbox::new(&t.to_string());
+                let mut checkbox = Checkbox::new(t);
                 checkbox.set_checked(true)?;
                 Ok(checkbox)
             })
             .collect::<Result<Vec<_>>>()?;
 
+        // Update tag filters; checking a tag selects every component carrying it
+        self.filter_panel.tag_filters = system
+            .tags()
+            .into_iter()
+            .map(|tag| {
+                let mut checkbox = Checkbox::new(tag);
+                let state = Arc::clone(&self.state);
+                let tag = tag.to_string();
+                checkbox.on_change(move |checked| {
+                    if !checked {
+                        return Ok(());
+                    }
+                    let selected: Vec<String> = {
+                        let system = state.get_system();
+                        let system = system.read()?;
+                        system.components_with_tag(&tag)
+                            .iter()
+                            .map(|c| c.id().to_string())
+                            .collect()
+                    };
+                    state.update_selection(selected)
+                });
+                checkbox
+            })
+            .collect();
+
         // Update range sliders based on system metadata
         if let Some((min, max)) = system.weight_range() {
             self.filter_panel.weight_range = Slider::new(min, max);
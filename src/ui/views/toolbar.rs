@@ -11,6 +11,8 @@ pub struct ToolbarView {
     export_button: Button,
     import_button: Button,
     zoom_buttons: Vec<Button>,
+    sampling_button: Button,
+    snap_button: Button,
 }
 
 impl ToolbarView {
@@ -34,6 +36,8 @@ impl ToolbarView {
                 Button::new("Zoom Out"),
                 Button::new("Fit View"),
             ],
+            sampling_button: Button::new("Toggle Sampled Preview"),
+            snap_button: Button::new("Snap to Grid"),
         }
     }
 
@@ -52,10 +56,13 @@ impl ToolbarView {
 
                 let mut config = state.get_ui_config()?;
                 config.layout.layout_type = layout_type;
+                let layout_config = config.layout.clone();
                 state.update_config(config)?;
 
                 let vis = state.get_visualization();
-                vis.write()?.update_layout()?;
+                let mut vis = vis.write()?;
+                vis.set_layout_config(layout_config);
+                vis.update_layout()?;
                 Ok(())
             });
         }
@@ -120,6 +127,41 @@ impl ToolbarView {
         }
         Ok(())
     }
+
+    fn setup_snap_button(&mut self) -> Result<()> {
+        let state = Arc::clone(&self.state);
+        self.snap_button.on_click(move || {
+            let mut config = state.get_ui_config()?;
+            config.layout.snap_to_grid = !config.layout.snap_to_grid;
+            let layout_config = config.layout.clone();
+            state.update_config(config)?;
+
+            let vis = state.get_visualization();
+            let mut vis = vis.write()?;
+            vis.set_layout_config(layout_config);
+            vis.update_layout()?;
+            Ok(())
+        });
+        Ok(())
+    }
+
+    fn setup_sampling_button(&mut self) -> Result<()> {
+        let state = Arc::clone(&self.state);
+        self.sampling_button.on_click(move || {
+            let mut config = state.get_ui_config()?;
+            config.sampling.enabled = !config.sampling.enabled;
+            let sampling_config = config.sampling.clone();
+            state.update_config(config)?;
+
+            let vis = state.get_visualization();
+            let mut vis = vis.write()?;
+            vis.set_sampling_config(sampling_config);
+            vis.update_layout()?;
+            vis.render_frame()?;
+            Ok(())
+        });
+        Ok(())
+    }
 }
 
 impl View for ToolbarView {
@@ -129,6 +171,8 @@ impl View for ToolbarView {
         self.setup_analysis_buttons()?;
         self.setup_io_buttons()?;
         self.setup_zoom_buttons()?;
+        self.setup_sampling_button()?;
+        self.setup_snap_button()?;
         Ok(())
     }
 
@@ -48,24 +48,54 @@ impl WidgetManager {
     }
 
     pub fn update_widgets(&mut self) -> Result<()> {
-        let system = self.state.get_system();
-        let system = system.read()?;
+        // Copy what we need out of the system before touching the
+        // visualization; views lock the visualization first, so holding both
+        // here in the other order could deadlock against them
+        let (components, relationships) = {
+            let system = self.state.get_system();
+            let system = system.read()?;
+            let components: Vec<_> = system.components()
+                .iter()
+                .map(|(id, component)| (*id, component.clone()))
+                .collect();
+            let relationships: Vec<_> = system.relationships()
+                .values()
+                .cloned()
+                .collect();
+            (components, relationships)
+        };
+
+        let visualization = self.state.get_visualization();
+        let visualization = visualization.read()?;
+        let focused = visualization.focused_node();
         
         // Update node widgets
-        self.node_widgets = system.components()
-            .iter()
-            .map(|(_, component)| NodeWidget::new(component.clone()))
+        self.node_widgets = components
+            .into_iter()
+            .map(|(id, component)| {
+                let mut widget = NodeWidget::new(component);
+                if let Some(position) = visualization.get_position(&id) {
+                    widget.set_center(position.x, position.y);
+                }
+                widget.set_focused(focused == Some(id));
+                widget
+            })
             .collect();
             
         // Update edge widgets
-        self.edge_widgets = system.relationships()
-            .iter()
-            .map(|(_, relationship)| EdgeWidget::new(relationship.clone()))
+        self.edge_widgets = relationships
+            .into_iter()
+            .map(EdgeWidget::new)
             .collect();
             
         Ok(())
     }
 
+    /// Focus ring of the node holding keyboard focus, if any
+    pub fn focus_ring(&self) -> Option<(f32, f32, f32, f32)> {
+        self.node_widgets.iter().find_map(NodeWidget::focus_ring)
+    }
+
     pub fn render_all(&self) -> Result<()> {
         for widget in &self.node_widgets {
             widget.render()?;
@@ -2,11 +2,15 @@ use crate::core::Component;
 use crate::error::Result;
 use super::{Widget, WidgetEvent};
 
+/// Gap between a node's outline and its keyboard focus ring
+const FOCUS_RING_PADDING: f32 = 4.0;
+
 pub struct NodeWidget {
     component: Component,
     position: (f32, f32),
     size: (f32, f32),
     is_selected: bool,
+    is_focused: bool,
     is_hovered: bool,
 }
 
@@ -17,6 +21,7 @@ impl NodeWidget {
             position: (0.0, 0.0),
             size: (50.0, 50.0), // Default size
             is_selected: false,
+            is_focused: false,
             is_hovered: false,
         }
     }
@@ -25,6 +30,13 @@ impl NodeWidget {
         self.position = (x, y);
     }
 
+    /// Place the node so that it is centered on `(x, y)`, e.g. a layout
+    /// position
+    pub fn set_center(&mut self, x: f32, y: f32) {
+        let (width, height) = self.size;
+        self.position = (x - width / 2.0, y - height / 2.0);
+    }
+
     pub fn set_size(&mut self, width: f32, height: f32) {
         self.size = (width, height);
     }
@@ -33,6 +45,10 @@ impl NodeWidget {
         self.is_selected = selected;
     }
 
+    pub fn set_focused(&mut self, focused: bool) {
+        self.is_focused = focused;
+    }
+
     pub fn contains_point(&self, x: f32, y: f32) -> bool {
         let (px, py) = self.position;
         let (width, height) = self.size;
@@ -40,6 +56,23 @@ impl NodeWidget {
         x >= px && x <= px + width && y >= py && y <= py + height
     }
 
+    /// Outline of the keyboard focus ring as `(x, y, width, height)`, or
+    /// `None` if the node isn't focused. The ring sits outside the node so it
+    /// stays visible alongside the selection highlight.
+    pub fn focus_ring(&self) -> Option<(f32, f32, f32, f32)> {
+        if !self.is_focused {
+            return None;
+        }
+        let (x, y) = self.position;
+        let (width, height) = self.size;
+        Some((
+            x - FOCUS_RING_PADDING,
+            y - FOCUS_RING_PADDING,
+            width + 2.0 * FOCUS_RING_PADDING,
+            height + 2.0 * FOCUS_RING_PADDING,
+        ))
+    }
+
     fn render_label(&self) -> Result<()> {
         // Render component label/name
         todo!("Implement label rendering")
@@ -69,7 +102,8 @@ impl NodeWidget {
 
 impl Widget for NodeWidget {
     fn render(&self) -> Result<()> {
-        // Basic rendering for now
+        // Basic rendering for now. The frontend draws the keyboard focus
+        // ring from `focus_ring`.
         Ok(())
     }
 
@@ -77,4 +111,20 @@ impl Widget for NodeWidget {
         // Basic interaction handling for now
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ComponentType;
+
+    #[test]
+    fn focus_ring_surrounds_focused_node() {
+        let mut widget = NodeWidget::new(Component::new("n".to_string(), ComponentType::Node));
+        widget.set_center(100.0, 50.0);
+        assert_eq!(widget.focus_ring(), None);
+
+        widget.set_focused(true);
+        assert_eq!(widget.focus_ring(), Some((71.0, 21.0, 58.0, 58.0)));
+        assert!(widget.render().is_ok());
+    }
+}
@@ -47,10 +47,8 @@ impl<T: Spatial + Debug> QuadTree<T> {
 
     fn get_target_child(children: &[QuadNode<T>; 4], pos: &Point2D) -> usize {
         for (i, child) in children.iter().enumerate() {
-            if let QuadNode::Leaf { bounds, .. } = child {
-                if bounds.contains_point(pos) {
-                    return i;
-                }
+            if child.bounds().contains_point(pos) {
+                return i;
             }
         }
         0 // Default to first quadrant if point doesn't fit exactly
@@ -118,6 +116,15 @@ impl<T: Spatial + Debug> QuadTree<T> {
     }
 }
 
+impl<T: Spatial + Debug> QuadNode<T> {
+    fn bounds(&self) -> &Bounds2D {
+        match self {
+            QuadNode::Leaf { bounds, .. } => bounds,
+            QuadNode::Internal { bounds, .. } => bounds,
+        }
+    }
+}
+
 impl<T: Spatial + Debug> SpatialIndex<T> for QuadTree<T> {
     fn insert(&mut self, item: T) {
         Self::insert_recursive(&mut self.root, item, 0);
@@ -207,4 +214,43 @@ impl<T: Spatial + Debug> SpatialIndex<T> for QuadTree<T> {
     fn len(&self) -> usize {
         self.size
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Item(Point2D);
+
+    impl Spatial for Item {
+        fn bounds(&self) -> Bounds2D {
+            Bounds2D::new(self.0.x, self.0.y, self.0.x, self.0.y)
+        }
+
+        fn position(&self) -> Point2D {
+            self.0
+        }
+    }
+
+    #[test]
+    fn inserts_below_internal_nodes_reach_their_quadrant() {
+        let mut tree = QuadTree::new(Bounds2D::new(0.0, 0.0, 100.0, 100.0));
+        // Enough points in the northeast quadrant to split the root and then
+        // the northeast child, so later inserts pass through internal nodes
+        let points: Vec<Point2D> = (0..40)
+            .map(|i| Point2D::new(60.0 + (i % 8) as f32 * 4.0, 60.0 + (i / 8) as f32 * 6.0))
+            .collect();
+        for &point in &points {
+            tree.insert(Item(point));
+        }
+        assert_eq!(tree.len(), points.len());
+
+        // Strictly inside the northeast quadrant, clear of its siblings
+        let found = tree.query(&Bounds2D::new(55.0, 55.0, 95.0, 95.0));
+        assert_eq!(found.len(), points.len());
+
+        let corner = tree.query(&Bounds2D::new(87.0, 83.0, 89.0, 85.0));
+        assert_eq!(corner.len(), 1);
+        assert_eq!(corner[0].position(), Point2D::new(88.0, 84.0));
+    }
+}
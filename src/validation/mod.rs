@@ -1,10 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::sync::Arc;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::core::{System, Component, Relationship};
+use crate::core::types::ComponentType;
+
+/// Metadata key set on per-component contexts by `validate_system`:
+/// `"true"` when the component is an endpoint of any relationship
+pub const CONNECTED_METADATA_KEY: &str = "connected";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ErrorType {
@@ -116,6 +121,7 @@ pub trait ErrorHandler: Send + Sync {
 pub struct ValidationEngine {
     validators: Vec<Box<dyn Validator>>,
     error_handlers: HashMap<ErrorType, Box<dyn ErrorHandler>>,
+    type_rules: HashMap<ComponentType, Vec<ValidationRule>>,
 }
 
 impl ValidationEngine {
@@ -123,6 +129,7 @@ impl ValidationEngine {
         Self {
             validators: Vec::new(),
             error_handlers: HashMap::new(),
+            type_rules: HashMap::new(),
         }
     }
 
@@ -130,6 +137,19 @@ impl ValidationEngine {
         self.validators.push(validator);
     }
 
+    /// Register a rule checked against every component of `component_type`.
+    /// Custom types are matched by name.
+    pub fn add_type_rule(&mut self, component_type: ComponentType, rule: ValidationRule) {
+        self.type_rules.entry(component_type).or_default().push(rule);
+    }
+
+    /// Register the built-in per-type rules from `default_type_rules`
+    pub fn add_default_type_rules(&mut self) {
+        for (component_type, rule) in default_type_rules() {
+            self.add_type_rule(component_type, rule);
+        }
+    }
+
     pub fn add_error_handler(&mut self, error_type: ErrorType, handler: Box<dyn ErrorHandler>) {
         self.error_handlers.insert(error_type, handler);
     }
@@ -142,8 +162,9 @@ impl ValidationEngine {
             metrics: ValidationMetrics::default(),
         };
 
+        let system = Arc::new(system.clone());
         let context = ValidationContext {
-            system: Some(Arc::new(system.clone())),
+            system: Some(Arc::clone(&system)),
             component: None,
             relationship: None,
             metadata: HashMap::new(),
@@ -160,10 +181,76 @@ impl ValidationEngine {
             result.metrics.error_count += validation.metrics.error_count;
         }
 
+        self.apply_type_rules(&system, &mut result);
+
         result.is_valid = result.errors.is_empty();
         result
     }
 
+    fn apply_type_rules(&self, system: &Arc<System>, result: &mut ValidationResult) {
+        if self.type_rules.is_empty() {
+            return;
+        }
+
+        // Collected once so rules needing connectivity stay linear in the
+        // number of relationships instead of rescanning them per component
+        let connected: HashSet<Uuid> = system.relationships.values()
+            .flat_map(|r| [r.source_id, r.target_id])
+            .collect();
+
+        for component in system.components.values() {
+            let Some(rules) = self.type_rules.get(&component.component_type) else {
+                continue;
+            };
+
+            let context = ValidationContext {
+                system: Some(Arc::clone(system)),
+                component: Some(Arc::new(component.clone())),
+                relationship: None,
+                metadata: HashMap::from([(
+                    CONNECTED_METADATA_KEY.to_string(),
+                    connected.contains(&component.id).to_string(),
+                )]),
+            };
+
+            for rule in rules {
+                result.metrics.total_validations += 1;
+                if (rule.check_function)(&context) {
+                    result.metrics.passed_validations += 1;
+                    continue;
+                }
+
+                result.metrics.failed_validations += 1;
+                let message = format!(
+                    "{} '{}' failed rule '{}': {}",
+                    component.type_name(),
+                    component.name,
+                    rule.name,
+                    rule.description
+                );
+                match rule.severity {
+                    ValidationSeverity::Error => {
+                        result.metrics.error_count += 1;
+                        result.errors.push(ValidationError {
+                            rule_id: rule.id,
+                            message,
+                            severity: rule.severity,
+                            context: context.clone(),
+                        });
+                    }
+                    ValidationSeverity::Warning | ValidationSeverity::Info => {
+                        result.metrics.warning_count += 1;
+                        result.warnings.push(ValidationWarning {
+                            rule_id: rule.id,
+                            message,
+                            context: context.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
     pub fn handle_error(&self, error: &SystemError) -> ErrorHandlingResult {
         if let Some(handler) = self.error_handlers.get(&error.error_type) {
             handler.handle_error(error)
@@ -182,7 +269,9 @@ impl ValidationEngine {
 }
 
 // Re-export commonly used items
-pub use self::validators::{SystemIntegrityValidator, ComponentValidator, RelationshipValidator};
+pub use self::validators::{
+    SystemIntegrityValidator, ComponentValidator, RelationshipValidator, default_type_rules,
+};
 pub use self::handlers::{DataCorruptionHandler, ConcurrencyHandler};
 
 // Submodules
@@ -1,4 +1,8 @@
-use super::{ValidationContext, ValidationResult, ValidationRule, ValidationSeverity, Validator};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::core::types::ComponentType;
+use super::{CONNECTED_METADATA_KEY, ValidationContext, ValidationResult, ValidationRule, ValidationSeverity, Validator};
 
 pub struct SystemIntegrityValidator;
 
@@ -61,4 +65,118 @@ impl Validator for RelationshipValidator {
     fn get_validation_rules(&self) -> Vec<ValidationRule> {
         Vec::new() // TODO: Implement relationship validation rules
     }
-} 
\ No newline at end of file
+}
+
+/// Built-in per-type rules: a Resource must declare a `capacity` property and
+/// an Interface must take part in at least one relationship
+pub fn default_type_rules() -> Vec<(ComponentType, ValidationRule)> {
+    vec![
+        (
+            ComponentType::Resource,
+            ValidationRule {
+                id: Uuid::new_v4(),
+                name: "resource-capacity".to_string(),
+                description: "Resources must have a capacity property".to_string(),
+                severity: ValidationSeverity::Error,
+                check_function: Arc::new(|context: &ValidationContext| {
+                    context.component.as_ref()
+                        .is_none_or(|c| c.properties.contains_key("capacity"))
+                }),
+            },
+        ),
+        (
+            ComponentType::Interface,
+            ValidationRule {
+                id: Uuid::new_v4(),
+                name: "interface-connected".to_string(),
+                description: "Interfaces must have at least one connection".to_string(),
+                severity: ValidationSeverity::Error,
+                check_function: Arc::new(|context: &ValidationContext| {
+                    if let Some(connected) = context.metadata.get(CONNECTED_METADATA_KEY) {
+                        return connected == "true";
+                    }
+                    let (Some(system), Some(component)) = (&context.system, &context.component) else {
+                        return true;
+                    };
+                    system.relationships.values()
+                        .any(|r| r.source_id == component.id || r.target_id == component.id)
+                }),
+            },
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Component, Relationship, RelationshipType, System};
+    use crate::validation::ValidationEngine;
+    use super::*;
+
+    fn engine() -> ValidationEngine {
+        let mut engine = ValidationEngine::new();
+        engine.add_default_type_rules();
+        engine
+    }
+
+    #[test]
+    fn resource_capacity_rule_checks_property() {
+        let mut system = System::new("test".to_string(), "test system".to_string());
+        let mut sized = Component::new("sized".to_string(), ComponentType::Resource);
+        sized.properties.insert("capacity".to_string(), "10".to_string());
+        let unsized_resource = Component::new("unsized".to_string(), ComponentType::Resource);
+        system.add_component(sized).unwrap();
+        system.add_component(unsized_resource).unwrap();
+
+        let result = engine().validate_system(&system);
+        assert!(!result.is_valid);
+        assert_eq!(result.metrics.total_validations, 2);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("'unsized'"));
+    }
+
+    #[test]
+    fn interface_rule_uses_precomputed_connections() {
+        let mut system = System::new("test".to_string(), "test system".to_string());
+        let linked = Component::new("linked".to_string(), ComponentType::Interface);
+        let lonely = Component::new("lonely".to_string(), ComponentType::Interface);
+        let peer = Component::new("peer".to_string(), ComponentType::Node);
+        let relationship = Relationship::new(peer.id, linked.id, RelationshipType::Flow);
+        system.add_component(linked).unwrap();
+        system.add_component(lonely).unwrap();
+        system.add_component(peer).unwrap();
+        system.add_relationship(relationship).unwrap();
+
+        let result = engine().validate_system(&system);
+        assert_eq!(result.errors.len(), 1);
+        assert!(result.errors[0].message.contains("'lonely'"));
+        assert_eq!(
+            result.errors[0].context.metadata.get(CONNECTED_METADATA_KEY).map(String::as_str),
+            Some("false")
+        );
+    }
+
+    #[test]
+    fn custom_type_rules_match_by_name() {
+        let mut engine = ValidationEngine::new();
+        engine.add_type_rule(
+            ComponentType::Custom("x".to_string()),
+            ValidationRule {
+                id: Uuid::new_v4(),
+                name: "custom-x".to_string(),
+                description: "Custom x components are flagged".to_string(),
+                severity: ValidationSeverity::Warning,
+                check_function: Arc::new(|_| false),
+            },
+        );
+
+        let mut system = System::new("test".to_string(), "test system".to_string());
+        system.add_component(Component::new("x".to_string(), ComponentType::Custom("x".to_string()))).unwrap();
+        system.add_component(Component::new("y".to_string(), ComponentType::Custom("y".to_string()))).unwrap();
+
+        let result = engine.validate_system(&system);
+        assert!(result.is_valid);
+        assert_eq!(result.metrics.total_validations, 1);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].message.contains("'x'"));
+    }
+}
@@ -73,7 +73,6 @@ impl ForceDirectedLayout {
                 if source == id1 || target == id1 {
                     let other_id = if source == id1 { target } else { source };
                     let pos2 = self.positions[&other_id];
-                    let dist = pos1.distance(&pos2);
                     force.x -= self.attraction * (pos1.x - pos2.x);
                     force.y -= self.attraction * (pos1.y - pos2.y);
                 }
@@ -90,6 +89,12 @@ impl ForceDirectedLayout {
         }
     }
 
+    pub fn set_position(&mut self, id: Uuid, position: Point) {
+        if let Some(pos) = self.positions.get_mut(&id) {
+            *pos = position;
+        }
+    }
+
     pub fn get_position(&self, id: &Uuid) -> Option<Point> {
         self.positions.get(id).copied()
     }
@@ -1,6 +1,17 @@
 use std::collections::HashMap;
 use uuid::Uuid;
 use super::force_directed::{Point, ForceDirectedLayout};
+use crate::util::spatial::{Point2D, Bounds2D, Spatial, SpatialIndex};
+use crate::util::spatial::quadtree::QuadTree;
+
+/// Maximum number of passes the de-overlap step makes before giving up
+const MAX_SEPARATION_ITERATIONS: usize = 50;
+
+/// Angle between successive nodes when fanning out coincident nodes
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+/// Node spacing used by the grid layout
+const GRID_SPACING: f32 = 50.0;
 
 /// Available layout algorithms
 #[derive(Debug, Clone, Copy)]
@@ -15,6 +26,27 @@ pub struct LayoutManager {
     algorithm: LayoutAlgorithm,
     force_directed: Option<ForceDirectedLayout>,
     positions: HashMap<Uuid, Point>,
+    min_separation: Option<f32>,
+    grid_snap: Option<f32>,
+    /// Positions the user dragged nodes to; layout steps leave them in place
+    pinned: HashMap<Uuid, Point>,
+}
+
+/// Node position entry used to index nodes during the de-overlap pass
+#[derive(Debug, Clone, Copy)]
+struct SeparationEntry {
+    index: usize,
+    position: Point2D,
+}
+
+impl Spatial for SeparationEntry {
+    fn bounds(&self) -> Bounds2D {
+        Bounds2D::new(self.position.x, self.position.y, self.position.x, self.position.y)
+    }
+
+    fn position(&self) -> Point2D {
+        self.position
+    }
 }
 
 impl LayoutManager {
@@ -23,9 +55,24 @@ impl LayoutManager {
             algorithm,
             force_directed: None,
             positions: HashMap::new(),
+            min_separation: None,
+            grid_snap: None,
+            pinned: HashMap::new(),
         }
     }
 
+    /// Set the cell size the grid layout aligns nodes to. `None` disables
+    /// snapping.
+    pub fn set_grid_snap(&mut self, cell_size: Option<f32>) {
+        self.grid_snap = cell_size.filter(|c| *c > 0.0);
+    }
+
+    /// Set the minimum distance enforced between node centers after each
+    /// layout step. `None` disables the de-overlap pass.
+    pub fn set_min_separation(&mut self, min_separation: Option<f32>) {
+        self.min_separation = min_separation.filter(|d| *d > 0.0);
+    }
+
     pub fn initialize_force_directed(&mut self) {
         self.force_directed = Some(ForceDirectedLayout::new(
             1.0,  // repulsion
@@ -52,7 +99,14 @@ impl LayoutManager {
     pub fn layout_grid(&mut self, node_ids: &[Uuid]) {
         let node_count = node_ids.len() as f32;
         let cols = (node_count.sqrt().ceil()) as i32;
-        let spacing = 50.0;
+        // Space nodes at least the minimum separation apart, so the
+        // de-overlap pass has nothing to nudge, and round up to whole cells
+        // so every node sits on an intersection of the snap grid
+        let spacing = GRID_SPACING.max(self.min_separation.unwrap_or(0.0));
+        let spacing = match self.grid_snap {
+            Some(cell_size) => (spacing / cell_size).ceil().max(1.0) * cell_size,
+            None => spacing,
+        };
 
         for (i, &id) in node_ids.iter().enumerate() {
             let row = (i as i32) / cols;
@@ -65,13 +119,15 @@ impl LayoutManager {
         }
     }
 
-    pub fn step(&mut self, node_ids: &[Uuid], edges: &[(Uuid, Uuid)]) {
+    /// Advance the layout by one step. Returns `false` if the minimum
+    /// separation pass ran and could not separate every pair.
+    pub fn step(&mut self, node_ids: &[Uuid], edges: &[(Uuid, Uuid)]) -> bool {
         match self.algorithm {
             LayoutAlgorithm::ForceDirected => {
                 if let Some(layout) = &mut self.force_directed {
                     // Ensure all nodes are initialized
                     for &id in node_ids {
-                        if !layout.get_position(&id).is_some() {
+                        if layout.get_position(&id).is_none() {
                             layout.add_node(id, None);
                         }
                     }
@@ -81,6 +137,220 @@ impl LayoutManager {
             LayoutAlgorithm::Circular => self.layout_circular(node_ids),
             LayoutAlgorithm::Grid => self.layout_grid(node_ids),
         }
+
+        for &id in node_ids {
+            if let Some(&position) = self.pinned.get(&id) {
+                self.set_position(id, position);
+            }
+        }
+
+        match self.min_separation {
+            Some(min_separation) => self.enforce_min_separation(node_ids, min_separation),
+            None => true,
+        }
+    }
+
+    /// Place `id` at `position` and keep it there through later layout
+    /// steps, e.g. after the user drags it
+    pub fn pin_position(&mut self, id: Uuid, position: Point) {
+        self.pinned.insert(id, position);
+        self.set_position(id, position);
+    }
+
+    /// Nudge overlapping nodes apart so node centers end up at least
+    /// `min_separation` apart. Dense layouts are first scaled uniformly and
+    /// only pairs that are still too close are moved afterwards, so the
+    /// overall shape of the layout is preserved. Pinned nodes never move;
+    /// their neighbors make room instead.
+    ///
+    /// Each pass only sees the positions from its start, so pushing one pair
+    /// apart can bring another too close until the next pass. Returns `true`
+    /// once a pass finds nothing to fix, and `false` if the passes ran out,
+    /// two pinned nodes are too close, or some nodes have no position yet.
+    pub fn enforce_min_separation(&mut self, node_ids: &[Uuid], min_separation: f32) -> bool {
+        let mut points: Vec<Point2D> = node_ids.iter()
+            .filter_map(|id| self.get_position(id))
+            .map(|p| Point2D::new(p.x, p.y))
+            .collect();
+        if points.len() != node_ids.len() {
+            return false;
+        }
+        if points.len() < 2 {
+            return true;
+        }
+
+        // Pairwise pushes separate a stack of coincident nodes only a
+        // little per pass, so fan each stack out on a golden-angle spiral
+        // first
+        Self::spread_coincident(&mut points, min_separation);
+
+        // A layout that is uniformly too dense (e.g. a tight grid or ring)
+        // cannot be fixed by local nudges in a few passes, so first scale it
+        // about its center until the typical nearest-neighbor gap fits.
+        if let Some(scale) = Self::density_scale(&points, min_separation) {
+            if let Some(bounds) = Bounds2D::from_points(&points) {
+                let center = bounds.center();
+                for point in points.iter_mut() {
+                    point.x = center.x + (point.x - center.x) * scale;
+                    point.y = center.y + (point.y - center.y) * scale;
+                }
+            }
+        }
+
+        let fixed: Vec<bool> = node_ids.iter().map(|id| self.pinned.contains_key(id)).collect();
+        for (i, id) in node_ids.iter().enumerate() {
+            if let Some(position) = self.pinned.get(id) {
+                points[i] = Point2D::new(position.x, position.y);
+            }
+        }
+
+        let mut converged = false;
+        for _ in 0..MAX_SEPARATION_ITERATIONS {
+            let bounds = match Bounds2D::from_points(&points) {
+                Some(bounds) => bounds,
+                None => return false,
+            };
+            let mut index = QuadTree::new(bounds);
+            for (i, &position) in points.iter().enumerate() {
+                index.insert(SeparationEntry { index: i, position });
+            }
+
+            let mut moved = false;
+            let mut stuck = false;
+            for i in 0..points.len() {
+                let center = points[i];
+                let query = Bounds2D::new(
+                    center.x - min_separation,
+                    center.y - min_separation,
+                    center.x + min_separation,
+                    center.y + min_separation,
+                );
+                let neighbors: Vec<usize> = index.query(&query)
+                    .into_iter()
+                    .map(|entry| entry.index)
+                    .filter(|&j| j > i)
+                    .collect();
+
+                for j in neighbors {
+                    let dx = points[j].x - points[i].x;
+                    let dy = points[j].y - points[i].y;
+                    let dist = (dx * dx + dy * dy).sqrt();
+                    if dist >= min_separation {
+                        continue;
+                    }
+                    if fixed[i] && fixed[j] {
+                        stuck = true;
+                        continue;
+                    }
+
+                    // Coincident nodes get a deterministic direction so they
+                    // still separate
+                    let (ux, uy) = if dist > f32::EPSILON {
+                        (dx / dist, dy / dist)
+                    } else {
+                        let angle = (i + j) as f32 * GOLDEN_ANGLE;
+                        (angle.cos(), angle.sin())
+                    };
+
+                    // Overshoot slightly so float rounding cannot leave the
+                    // pair marginally too close. A pinned node's partner
+                    // takes the whole push.
+                    let shift = (min_separation - dist) * 1.01;
+                    let (shift_i, shift_j) = match (fixed[i], fixed[j]) {
+                        (true, _) => (0.0, shift),
+                        (_, true) => (shift, 0.0),
+                        _ => (shift * 0.5, shift * 0.5),
+                    };
+                    points[i].x -= ux * shift_i;
+                    points[i].y -= uy * shift_i;
+                    points[j].x += ux * shift_j;
+                    points[j].y += uy * shift_j;
+                    moved = true;
+                }
+            }
+
+            if !moved {
+                converged = !stuck;
+                break;
+            }
+        }
+
+        for (&id, point) in node_ids.iter().zip(points) {
+            self.set_position(id, Point::new(point.x, point.y));
+        }
+        converged
+    }
+
+    /// Move nodes sharing a position onto a spiral around it, roughly
+    /// `min_separation` apart. The first node of each stack stays put.
+    fn spread_coincident(points: &mut [Point2D], min_separation: f32) {
+        let mut stacks: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+        for (i, point) in points.iter().enumerate() {
+            stacks.entry((point.x.to_bits(), point.y.to_bits())).or_default().push(i);
+        }
+
+        for stack in stacks.values().filter(|stack| stack.len() > 1) {
+            let center = points[stack[0]];
+            for (k, &i) in stack.iter().enumerate().skip(1) {
+                let radius = min_separation * (k as f32).sqrt();
+                let angle = k as f32 * GOLDEN_ANGLE;
+                points[i] = Point2D::new(
+                    center.x + radius * angle.cos(),
+                    center.y + radius * angle.sin(),
+                );
+            }
+        }
+    }
+
+    /// Scale factor that brings the median nearest-neighbor distance up to
+    /// `min_separation`, or `None` if the layout is already sparse enough.
+    fn density_scale(points: &[Point2D], min_separation: f32) -> Option<f32> {
+        let bounds = Bounds2D::from_points(points)?;
+        let mut index = QuadTree::new(bounds);
+        for (i, &position) in points.iter().enumerate() {
+            index.insert(SeparationEntry { index: i, position });
+        }
+
+        let mut nearest: Vec<f32> = points.iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let query = Bounds2D::new(
+                    p.x - min_separation,
+                    p.y - min_separation,
+                    p.x + min_separation,
+                    p.y + min_separation,
+                );
+                index.query(&query)
+                    .into_iter()
+                    .filter(|entry| entry.index != i)
+                    .map(|entry| entry.position.distance_to(p))
+                    .fold(min_separation, f32::min)
+            })
+            .collect();
+        nearest.sort_by(f32::total_cmp);
+
+        let median = nearest[nearest.len() / 2];
+        // Overshoot like the pairwise pass: far from the origin a shortfall
+        // of a fraction of a unit is below f32 resolution and can't be
+        // nudged away afterwards
+        if median > f32::EPSILON && median < min_separation {
+            Some(min_separation / median * 1.01)
+        } else {
+            None
+        }
+    }
+
+    pub fn set_position(&mut self, id: Uuid, position: Point) {
+        match self.algorithm {
+            LayoutAlgorithm::ForceDirected => {
+                if let Some(layout) = &mut self.force_directed {
+                    layout.set_position(id, position);
+                }
+            }
+            _ => {
+                self.positions.insert(id, position);
+            }
+        }
     }
 
     pub fn get_position(&self, id: &Uuid) -> Option<Point> {
@@ -91,4 +361,135 @@ impl LayoutManager {
             _ => self.positions.get(id).copied(),
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Round `point` to the nearest intersection of a grid with `cell_size`
+/// spacing
+pub fn snap_to_grid(point: Point, cell_size: f32) -> Point {
+    if cell_size <= 0.0 {
+        return point;
+    }
+    Point::new(
+        (point.x / cell_size).round() * cell_size,
+        (point.y / cell_size).round() * cell_size,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_ids(count: usize) -> Vec<Uuid> {
+        (0..count).map(|_| Uuid::new_v4()).collect()
+    }
+
+    /// Smallest distance between any two node centers
+    fn min_pair_distance(manager: &LayoutManager, node_ids: &[Uuid]) -> f32 {
+        let points: Vec<Point> = node_ids.iter()
+            .map(|id| manager.get_position(id).unwrap())
+            .collect();
+        let mut min = f32::INFINITY;
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                let dx = points[j].x - points[i].x;
+                let dy = points[j].y - points[i].y;
+                min = min.min((dx * dx + dy * dy).sqrt());
+            }
+        }
+        min
+    }
+
+    #[test]
+    fn dense_grid_is_separated() {
+        let ids = node_ids(400);
+        let mut manager = LayoutManager::new(LayoutAlgorithm::Grid);
+        manager.set_min_separation(Some(80.0));
+        assert!(manager.step(&ids, &[]));
+
+        assert!(min_pair_distance(&manager, &ids) >= 80.0);
+    }
+
+    #[test]
+    fn dense_circle_is_separated() {
+        let ids = node_ids(400);
+        let mut manager = LayoutManager::new(LayoutAlgorithm::Circular);
+        manager.set_min_separation(Some(80.0));
+        assert!(manager.step(&ids, &[]));
+
+        assert!(min_pair_distance(&manager, &ids) >= 80.0);
+    }
+
+    #[test]
+    fn coincident_nodes_are_separated() {
+        // Two overlapping stacks of coincident nodes
+        let ids = node_ids(100);
+        let mut manager = LayoutManager::new(LayoutAlgorithm::Grid);
+        for (i, &id) in ids.iter().enumerate() {
+            let x = if i % 2 == 0 { 10.0 } else { 15.0 };
+            manager.set_position(id, Point::new(x, 10.0));
+        }
+        assert!(manager.enforce_min_separation(&ids, 30.0));
+
+        assert!(min_pair_distance(&manager, &ids) >= 30.0);
+    }
+
+    #[test]
+    fn sparse_layout_is_left_alone() {
+        let ids = node_ids(9);
+        let mut manager = LayoutManager::new(LayoutAlgorithm::Grid);
+        manager.layout_grid(&ids);
+        let before: Vec<Point> = ids.iter().map(|id| manager.get_position(id).unwrap()).collect();
+
+        assert!(manager.enforce_min_separation(&ids, 20.0));
+
+        for (id, expected) in ids.iter().zip(before) {
+            let actual = manager.get_position(id).unwrap();
+            assert_eq!((actual.x, actual.y), (expected.x, expected.y));
+        }
+    }
+
+    #[test]
+    fn snapped_grid_survives_separation() {
+        let ids = node_ids(16);
+        let mut manager = LayoutManager::new(LayoutAlgorithm::Grid);
+        manager.set_grid_snap(Some(30.0));
+        manager.set_min_separation(Some(70.0));
+        assert!(manager.step(&ids, &[]));
+
+        assert!(min_pair_distance(&manager, &ids) >= 70.0);
+        for id in &ids {
+            let position = manager.get_position(id).unwrap();
+            let snapped = snap_to_grid(position, 30.0);
+            assert_eq!((position.x, position.y), (snapped.x, snapped.y));
+        }
+    }
+
+    #[test]
+    fn neighbors_make_room_for_pinned_node() {
+        let ids = node_ids(9);
+        let mut manager = LayoutManager::new(LayoutAlgorithm::Grid);
+        manager.set_min_separation(Some(50.0));
+        assert!(manager.step(&ids, &[]));
+
+        // Drop a node right next to another one
+        manager.pin_position(ids[0], Point::new(55.0, 5.0));
+        assert!(manager.step(&ids, &[]));
+
+        let pinned = manager.get_position(&ids[0]).unwrap();
+        assert_eq!((pinned.x, pinned.y), (55.0, 5.0));
+        assert!(min_pair_distance(&manager, &ids) >= 50.0);
+    }
+
+    #[test]
+    fn overlapping_pinned_nodes_are_reported() {
+        let ids = node_ids(3);
+        let mut manager = LayoutManager::new(LayoutAlgorithm::Grid);
+        manager.set_min_separation(Some(50.0));
+        assert!(manager.step(&ids, &[]));
+
+        // Neither pinned node may move, so the pair stays too close
+        manager.pin_position(ids[0], Point::new(0.0, 0.0));
+        manager.pin_position(ids[1], Point::new(10.0, 0.0));
+        assert!(!manager.step(&ids, &[]));
+    }
+}
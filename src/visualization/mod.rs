@@ -1,37 +1,238 @@
-use crate::error::Result;
+use uuid::Uuid;
+
+use crate::error::{Error, Result};
 use crate::core::System;
-use crate::ui::LayoutConfig;
+use crate::ui::{LayoutConfig, LayoutType, SamplingConfig};
+
+pub mod force_directed;
+pub mod layout;
+pub mod navigation;
+pub mod sampling;
+
+pub use force_directed::{Point, ForceDirectedLayout};
+pub use layout::{LayoutAlgorithm, LayoutManager};
+pub use navigation::{Direction, FocusOrder, NavigationIndex};
+pub use sampling::SampleInfo;
 
 pub struct VisualizationEngine {
     layout_config: LayoutConfig,
+    layout_manager: LayoutManager,
+    sampling: SamplingConfig,
+    all_node_ids: Vec<Uuid>,
+    all_edges: Vec<(Uuid, Uuid)>,
+    /// Nodes and edges actually laid out and rendered; a sample of the
+    /// full graph while the preview is active
+    node_ids: Vec<Uuid>,
+    edges: Vec<(Uuid, Uuid)>,
+    sample: Option<SampleInfo>,
+    focused_node: Option<Uuid>,
+    /// Arrow-key lookup over the current positions; dropped whenever nodes
+    /// move and rebuilt on the next arrow key
+    navigation: Option<NavigationIndex>,
+    camera_center: Point,
     initialized: bool,
 }
 
 impl VisualizationEngine {
     pub fn new(config: LayoutConfig) -> Self {
+        let layout_manager = Self::create_layout_manager(&config);
         Self {
             layout_config: config,
+            layout_manager,
+            sampling: SamplingConfig::default(),
+            all_node_ids: Vec::new(),
+            all_edges: Vec::new(),
+            node_ids: Vec::new(),
+            edges: Vec::new(),
+            sample: None,
+            focused_node: None,
+            navigation: None,
+            camera_center: Point::new(0.0, 0.0),
             initialized: false,
         }
     }
 
+    fn create_layout_manager(config: &LayoutConfig) -> LayoutManager {
+        let algorithm = match config.layout_type {
+            LayoutType::Force => LayoutAlgorithm::ForceDirected,
+            LayoutType::Grid => LayoutAlgorithm::Grid,
+            LayoutType::Circular => LayoutAlgorithm::Circular,
+            // No dedicated hierarchical layout yet; fall back to force-directed
+            LayoutType::Hierarchical => LayoutAlgorithm::ForceDirected,
+        };
+
+        let mut manager = LayoutManager::new(algorithm);
+        if matches!(algorithm, LayoutAlgorithm::ForceDirected) {
+            manager.initialize_force_directed();
+        }
+        Self::configure_layout_manager(&mut manager, config);
+        manager
+    }
+
+    fn configure_layout_manager(manager: &mut LayoutManager, config: &LayoutConfig) {
+        manager.set_min_separation(config.min_node_separation);
+        manager.set_grid_snap(config.snap_to_grid.then_some(config.grid_cell_size));
+    }
+
+    pub fn set_layout_config(&mut self, config: LayoutConfig) {
+        // Keep current positions unless the layout algorithm itself changes
+        if config.layout_type == self.layout_config.layout_type {
+            Self::configure_layout_manager(&mut self.layout_manager, &config);
+        } else {
+            self.layout_manager = Self::create_layout_manager(&config);
+            self.navigation = None;
+        }
+        self.layout_config = config;
+    }
+
+    /// Change sampling settings and re-select the rendered nodes
+    pub fn set_sampling_config(&mut self, config: SamplingConfig) {
+        self.sampling = config;
+        self.apply_sampling();
+    }
+
+    /// Preview size when only a sample of the graph is rendered
+    pub fn sample_info(&self) -> Option<SampleInfo> {
+        self.sample
+    }
+
+    fn apply_sampling(&mut self) {
+        let total = self.all_node_ids.len() + self.all_edges.len();
+        let sampled = self.sampling.enabled
+            && total > self.sampling.threshold
+            && self.all_node_ids.len() > self.sampling.max_nodes;
+
+        if sampled {
+            let (nodes, edges) = sampling::top_degree_sample(
+                &self.all_node_ids,
+                &self.all_edges,
+                self.sampling.max_nodes,
+            );
+            self.sample = Some(SampleInfo {
+                shown_nodes: nodes.len(),
+                total_nodes: self.all_node_ids.len(),
+                shown_edges: edges.len(),
+                total_edges: self.all_edges.len(),
+            });
+            self.node_ids = nodes;
+            self.edges = edges;
+        } else {
+            self.sample = None;
+            self.node_ids = self.all_node_ids.clone();
+            self.edges = self.all_edges.clone();
+        }
+
+        if let Some(focused) = self.focused_node {
+            if self.node_ids.binary_search(&focused).is_err() {
+                self.focused_node = None;
+            }
+        }
+        self.navigation = None;
+    }
+
+    pub fn get_position(&self, id: &Uuid) -> Option<Point> {
+        self.layout_manager.get_position(id)
+    }
+
+    /// Move a dragged node to `position` and keep it there through later
+    /// layout updates. With grid snapping on, the node lands on the nearest
+    /// grid intersection; `invert_snap` (the snap modifier key) flips the
+    /// setting for this drag. Returns where the node was placed.
+    pub fn drag_node(&mut self, id: &Uuid, position: Point, invert_snap: bool) -> Result<Point> {
+        if self.get_position(id).is_none() {
+            return Err(Error::component_not_found(*id));
+        }
+
+        let position = if self.layout_config.snap_to_grid != invert_snap {
+            layout::snap_to_grid(position, self.layout_config.grid_cell_size)
+        } else {
+            position
+        };
+        self.layout_manager.pin_position(*id, position);
+        self.navigation = None;
+        Ok(position)
+    }
+
+    pub fn focused_node(&self) -> Option<Uuid> {
+        self.focused_node
+    }
+
+    pub fn camera_center(&self) -> Point {
+        self.camera_center
+    }
+
+    /// Give `id` keyboard focus and center the camera on it
+    pub fn focus_node(&mut self, id: &Uuid) -> Result<()> {
+        self.focused_node = Some(*id);
+        if let Some(position) = self.get_position(id) {
+            self.camera_center = position;
+        }
+        Ok(())
+    }
+
+    /// Move focus to the next (or previous) node in Tab order
+    pub fn focus_next(&mut self, order: FocusOrder, reverse: bool) -> Result<Option<Uuid>> {
+        let order = navigation::focus_order(&self.positioned_nodes(), order);
+        let next = navigation::next_in_order(&order, self.focused_node, reverse);
+        if let Some(id) = next {
+            self.focus_node(&id)?;
+        }
+        Ok(next)
+    }
+
+    /// Move focus to the nearest node in `direction` from the focused node
+    pub fn focus_direction(&mut self, direction: Direction) -> Result<Option<Uuid>> {
+        let Some(current) = self.focused_node else {
+            return Ok(None);
+        };
+        if self.navigation.is_none() {
+            self.navigation = NavigationIndex::new(&self.positioned_nodes());
+        }
+        let next = self.navigation.as_ref()
+            .and_then(|index| index.neighbor(current, direction));
+        if let Some(id) = next {
+            self.focus_node(&id)?;
+        }
+        Ok(next)
+    }
+
+    fn positioned_nodes(&self) -> Vec<(Uuid, Point)> {
+        self.node_ids.iter()
+            .filter_map(|id| self.get_position(id).map(|p| (*id, p)))
+            .collect()
+    }
+
     pub fn initialize(&mut self) -> Result<()> {
         // Basic initialization for now
         self.initialized = true;
         Ok(())
     }
 
-    pub fn update_graph(&mut self, _system: &System) -> Result<()> {
+    pub fn update_graph(&mut self, system: &System) -> Result<()> {
         if !self.initialized {
             return Ok(());
         }
-        Ok(())
+
+        // Sort ids so layouts are stable across updates
+        let mut node_ids: Vec<Uuid> = system.components.keys().copied().collect();
+        node_ids.sort();
+        self.all_node_ids = node_ids;
+        self.all_edges = system.relationships.values()
+            .map(|r| (r.source_id, r.target_id))
+            .collect();
+        self.apply_sampling();
+
+        self.update_layout()
     }
 
     pub fn update_layout(&mut self) -> Result<()> {
         if !self.initialized {
             return Ok(());
         }
+        if !self.layout_manager.step(&self.node_ids, &self.edges) {
+            tracing::warn!("Layout step left some nodes closer than the minimum separation");
+        }
+        self.navigation = None;
         Ok(())
     }
 
@@ -76,4 +277,129 @@ impl VisualizationEngine {
         }
         Ok(())
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Component, Relationship};
+    use crate::core::types::{ComponentType, RelationshipType};
+
+    fn grid_engine() -> VisualizationEngine {
+        let config = LayoutConfig {
+            layout_type: LayoutType::Grid,
+            ..LayoutConfig::default()
+        };
+        let mut engine = VisualizationEngine::new(config);
+        engine.initialize().unwrap();
+        engine
+    }
+
+    #[test]
+    fn large_graph_renders_sample_keeping_hubs() {
+        // Three hubs linked to every other node, plus a chain of leaves
+        let mut system = System::new("large".to_string(), "sampled".to_string());
+        let ids: Vec<Uuid> = (0..300)
+            .map(|i| {
+                let component = Component::new(format!("n{}", i), ComponentType::Node);
+                let id = component.id;
+                system.add_component(component).unwrap();
+                id
+            })
+            .collect();
+        let hubs = &ids[..3];
+        for hub in hubs {
+            for leaf in &ids[3..] {
+                system.add_relationship(Relationship::new(*hub, *leaf, RelationshipType::Association)).unwrap();
+            }
+        }
+        for pair in ids[3..].windows(2) {
+            system.add_relationship(Relationship::new(pair[0], pair[1], RelationshipType::Association)).unwrap();
+        }
+
+        let mut engine = grid_engine();
+        engine.set_sampling_config(SamplingConfig {
+            enabled: true,
+            threshold: 500,
+            max_nodes: 50,
+        });
+        engine.update_graph(&system).unwrap();
+
+        let info = engine.sample_info().unwrap();
+        assert!(info.shown_nodes <= 50);
+        assert_eq!(info.total_nodes, 300);
+        assert_eq!(info.total_edges, system.relationships.len());
+        assert!(hubs.iter().all(|hub| engine.node_ids.contains(hub)));
+        assert!(hubs.iter().all(|hub| engine.get_position(hub).is_some()));
+        assert_eq!(info.indicator(), format!("Showing {} of 300 nodes", info.shown_nodes));
+
+        // Turning sampling off renders everything again
+        engine.set_sampling_config(SamplingConfig {
+            enabled: false,
+            ..SamplingConfig::default()
+        });
+        assert!(engine.sample_info().is_none());
+        assert_eq!(engine.node_ids.len(), 300);
+    }
+
+    fn engine_with_nodes(layout_type: LayoutType, snap_to_grid: bool, count: usize) -> (VisualizationEngine, Vec<Uuid>) {
+        let mut system = System::new("drag".to_string(), "drag test".to_string());
+        let mut ids: Vec<Uuid> = (0..count)
+            .map(|i| {
+                let component = Component::new(format!("n{}", i), ComponentType::Node);
+                let id = component.id;
+                system.add_component(component).unwrap();
+                id
+            })
+            .collect();
+        ids.sort();
+
+        let mut engine = VisualizationEngine::new(LayoutConfig {
+            layout_type,
+            snap_to_grid,
+            grid_cell_size: 25.0,
+            ..LayoutConfig::default()
+        });
+        engine.initialize().unwrap();
+        engine.update_graph(&system).unwrap();
+        (engine, ids)
+    }
+
+    #[test]
+    fn dragged_nodes_snap_and_stay_put() {
+        let (mut engine, ids) = engine_with_nodes(LayoutType::Grid, true, 4);
+
+        let snapped = engine.drag_node(&ids[0], Point::new(37.0, 61.0), false).unwrap();
+        assert_eq!((snapped.x, snapped.y), (25.0, 50.0));
+        // Holding the modifier places the node exactly
+        let free = engine.drag_node(&ids[1], Point::new(137.0, 61.0), true).unwrap();
+        assert_eq!((free.x, free.y), (137.0, 61.0));
+
+        // Re-running the layout keeps both where they were dropped
+        engine.update_layout().unwrap();
+        let position = engine.get_position(&ids[0]).unwrap();
+        assert_eq!((position.x, position.y), (25.0, 50.0));
+        let position = engine.get_position(&ids[1]).unwrap();
+        assert_eq!((position.x, position.y), (137.0, 61.0));
+
+        assert!(engine.drag_node(&Uuid::new_v4(), Point::new(0.0, 0.0), false).is_err());
+    }
+
+    #[test]
+    fn drag_without_snap_and_inverted_snap() {
+        let (mut engine, ids) = engine_with_nodes(LayoutType::Force, false, 3);
+
+        let free = engine.drag_node(&ids[0], Point::new(37.0, 61.0), false).unwrap();
+        assert_eq!((free.x, free.y), (37.0, 61.0));
+        let snapped = engine.drag_node(&ids[1], Point::new(-37.0, 13.0), true).unwrap();
+        assert_eq!((snapped.x, snapped.y), (-25.0, 25.0));
+
+        // Force steps pull other nodes around but not the dragged ones
+        for _ in 0..5 {
+            engine.update_layout().unwrap();
+        }
+        let position = engine.get_position(&ids[0]).unwrap();
+        assert_eq!((position.x, position.y), (37.0, 61.0));
+        let position = engine.get_position(&ids[1]).unwrap();
+        assert_eq!((position.x, position.y), (-25.0, 25.0));
+    }
+}
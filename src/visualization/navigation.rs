@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use uuid::Uuid;
+use super::force_directed::Point;
+use crate::util::spatial::{Point2D, Bounds2D, Spatial, SpatialIndex};
+use crate::util::spatial::quadtree::QuadTree;
+
+/// Order in which Tab cycles focus through nodes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusOrder {
+    /// Sorted by component id; stable regardless of layout
+    Id,
+    /// Row-major by position: top to bottom, then left to right
+    Spatial,
+}
+
+/// Screen direction for arrow-key navigation (y grows downwards)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NodeEntry {
+    id: Uuid,
+    position: Point2D,
+}
+
+impl Spatial for NodeEntry {
+    fn bounds(&self) -> Bounds2D {
+        Bounds2D::new(self.position.x, self.position.y, self.position.x, self.position.y)
+    }
+
+    fn position(&self) -> Point2D {
+        self.position
+    }
+}
+
+/// Sort nodes into Tab order
+pub fn focus_order(nodes: &[(Uuid, Point)], order: FocusOrder) -> Vec<Uuid> {
+    let mut nodes = nodes.to_vec();
+    match order {
+        FocusOrder::Id => nodes.sort_by_key(|(id, _)| *id),
+        FocusOrder::Spatial => nodes.sort_by(|(a_id, a), (b_id, b)| {
+            a.y.partial_cmp(&b.y)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal))
+                .then(a_id.cmp(b_id))
+        }),
+    }
+    nodes.into_iter().map(|(id, _)| id).collect()
+}
+
+/// Node after (or before, if `reverse`) `current` in `order`, wrapping
+/// around. With no current focus this is the first (or last) node.
+pub fn next_in_order(order: &[Uuid], current: Option<Uuid>, reverse: bool) -> Option<Uuid> {
+    if order.is_empty() {
+        return None;
+    }
+
+    let len = order.len();
+    let index = match current.and_then(|id| order.iter().position(|n| *n == id)) {
+        Some(i) if reverse => (i + len - 1) % len,
+        Some(i) => (i + 1) % len,
+        None if reverse => len - 1,
+        None => 0,
+    };
+    Some(order[index])
+}
+
+/// Spatial index over node positions for arrow-key navigation. Build it
+/// once per layout and reuse it for every key press.
+pub struct NavigationIndex {
+    tree: QuadTree<NodeEntry>,
+    positions: HashMap<Uuid, Point2D>,
+    bounds: Bounds2D,
+    /// First search distance, about the typical spacing between nodes
+    initial_reach: f32,
+}
+
+impl NavigationIndex {
+    /// Index `nodes`, or `None` if there are none
+    pub fn new(nodes: &[(Uuid, Point)]) -> Option<Self> {
+        let points: Vec<Point2D> = nodes.iter().map(|(_, p)| Point2D::new(p.x, p.y)).collect();
+        let bounds = Bounds2D::from_points(&points)?;
+
+        let mut tree = QuadTree::new(bounds);
+        let mut positions = HashMap::with_capacity(nodes.len());
+        for (&(id, _), &position) in nodes.iter().zip(points.iter()) {
+            tree.insert(NodeEntry { id, position });
+            positions.insert(id, position);
+        }
+
+        let extent = bounds.width().max(bounds.height());
+        let initial_reach = (extent / (nodes.len() as f32).sqrt()).max(1.0);
+
+        Some(Self {
+            tree,
+            positions,
+            bounds,
+            initial_reach,
+        })
+    }
+
+    /// Nearest node to `from` lying in `direction`, i.e. within the 90 degree
+    /// cone centered on that direction. The search box grows outwards from
+    /// `from` until it holds a match or covers the rest of the layout.
+    pub fn neighbor(&self, from: Uuid, direction: Direction) -> Option<Uuid> {
+        let origin = *self.positions.get(&from)?;
+
+        // Farthest any node can lie along the arrow direction
+        let max_reach = match direction {
+            Direction::Up => origin.y - self.bounds.min_y,
+            Direction::Down => self.bounds.max_y - origin.y,
+            Direction::Left => origin.x - self.bounds.min_x,
+            Direction::Right => self.bounds.max_x - origin.x,
+        };
+        if !max_reach.is_finite() {
+            return None;
+        }
+
+        let mut reach = self.initial_reach;
+        loop {
+            let query = match direction {
+                Direction::Up => Bounds2D::new(origin.x - reach, origin.y - reach, origin.x + reach, origin.y),
+                Direction::Down => Bounds2D::new(origin.x - reach, origin.y, origin.x + reach, origin.y + reach),
+                Direction::Left => Bounds2D::new(origin.x - reach, origin.y - reach, origin.x, origin.y + reach),
+                Direction::Right => Bounds2D::new(origin.x, origin.y - reach, origin.x + reach, origin.y + reach),
+            };
+            let best = self.tree.query(&query)
+                .into_iter()
+                .filter(|entry| entry.id != from && in_cone(origin, entry.position, direction))
+                .map(|entry| (entry.position.distance_to(&origin), entry.id))
+                .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            // Every node in the cone within `reach` of the origin falls inside
+            // the box, so a match that close can't be beaten further out
+            match best {
+                Some((distance, id)) if distance <= reach => return Some(id),
+                _ if reach >= max_reach => return best.map(|(_, id)| id),
+                _ => reach *= 2.0,
+            }
+        }
+    }
+}
+
+fn in_cone(origin: Point2D, point: Point2D, direction: Direction) -> bool {
+    let dx = point.x - origin.x;
+    let dy = point.y - origin.y;
+    match direction {
+        Direction::Up => -dy > 0.0 && dx.abs() <= -dy,
+        Direction::Down => dy > 0.0 && dx.abs() <= dy,
+        Direction::Left => -dx > 0.0 && dy.abs() <= -dx,
+        Direction::Right => dx > 0.0 && dy.abs() <= dx,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3x3 grid, 50 apart, with ids in ascending order row by row
+    fn grid() -> Vec<(Uuid, Point)> {
+        let mut ids: Vec<Uuid> = (0..9).map(|_| Uuid::new_v4()).collect();
+        ids.sort();
+        ids.into_iter()
+            .enumerate()
+            .map(|(i, id)| (id, Point::new((i % 3) as f32 * 50.0, (i / 3) as f32 * 50.0)))
+            .collect()
+    }
+
+    #[test]
+    fn focus_order_by_id_and_position() {
+        let nodes = grid();
+        let expected: Vec<Uuid> = nodes.iter().map(|(id, _)| *id).collect();
+        assert_eq!(focus_order(&nodes, FocusOrder::Id), expected);
+
+        // Positions listed bottom row first still come out row-major
+        let mut reversed = nodes.clone();
+        reversed.reverse();
+        assert_eq!(focus_order(&reversed, FocusOrder::Spatial), expected);
+    }
+
+    #[test]
+    fn tab_order_wraps_both_ways() {
+        let order = focus_order(&grid(), FocusOrder::Id);
+
+        assert_eq!(next_in_order(&order, None, false), Some(order[0]));
+        assert_eq!(next_in_order(&order, None, true), Some(order[8]));
+        assert_eq!(next_in_order(&order, Some(order[8]), false), Some(order[0]));
+        // Shift-Tab from the first node wraps to the last
+        assert_eq!(next_in_order(&order, Some(order[0]), true), Some(order[8]));
+        assert_eq!(next_in_order(&order, Some(order[4]), true), Some(order[3]));
+        assert_eq!(next_in_order(&[], None, false), None);
+    }
+
+    #[test]
+    fn arrow_keys_pick_neighbor_in_direction() {
+        let nodes = grid();
+        let id = |i: usize| nodes[i].0;
+        let index = NavigationIndex::new(&nodes).unwrap();
+
+        assert_eq!(index.neighbor(id(4), Direction::Up), Some(id(1)));
+        assert_eq!(index.neighbor(id(4), Direction::Down), Some(id(7)));
+        assert_eq!(index.neighbor(id(4), Direction::Left), Some(id(3)));
+        assert_eq!(index.neighbor(id(4), Direction::Right), Some(id(5)));
+        assert_eq!(index.neighbor(id(0), Direction::Up), None);
+        assert_eq!(index.neighbor(id(8), Direction::Right), None);
+    }
+
+    #[test]
+    fn arrow_search_reaches_distant_nodes() {
+        let mut nodes = grid();
+        let far = Uuid::new_v4();
+        nodes.push((far, Point::new(2_000.0, 60.0)));
+        let index = NavigationIndex::new(&nodes).unwrap();
+
+        assert_eq!(index.neighbor(nodes[5].0, Direction::Right), Some(far));
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Size of a sampled preview relative to the full graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleInfo {
+    pub shown_nodes: usize,
+    pub total_nodes: usize,
+    pub shown_edges: usize,
+    pub total_edges: usize,
+}
+
+impl SampleInfo {
+    /// Indicator text shown while the preview is active
+    pub fn indicator(&self) -> String {
+        format!("Showing {} of {} nodes", self.shown_nodes, self.total_nodes)
+    }
+}
+
+/// Keep the `max_nodes` highest-degree nodes (ties broken by id) and the
+/// edges running between them. Returned nodes stay in their input order.
+pub fn top_degree_sample(
+    node_ids: &[Uuid],
+    edges: &[(Uuid, Uuid)],
+    max_nodes: usize,
+) -> (Vec<Uuid>, Vec<(Uuid, Uuid)>) {
+    let mut degree: HashMap<Uuid, usize> = node_ids.iter().map(|id| (*id, 0)).collect();
+    for (source, target) in edges {
+        if let Some(d) = degree.get_mut(source) {
+            *d += 1;
+        }
+        if let Some(d) = degree.get_mut(target) {
+            *d += 1;
+        }
+    }
+
+    let mut ranked: Vec<Uuid> = node_ids.to_vec();
+    ranked.sort_by(|a, b| degree[b].cmp(&degree[a]).then_with(|| a.cmp(b)));
+    let kept: HashSet<Uuid> = ranked.into_iter().take(max_nodes).collect();
+
+    let nodes = node_ids.iter()
+        .filter(|id| kept.contains(id))
+        .copied()
+        .collect();
+    let edges = edges.iter()
+        .filter(|(source, target)| kept.contains(source) && kept.contains(target))
+        .copied()
+        .collect();
+
+    (nodes, edges)
+}